@@ -0,0 +1,650 @@
+use std::collections::HashMap;
+
+use async_trait::async_trait;
+use chrono::{DateTime, Datelike, NaiveDate, NaiveTime};
+use chrono_tz::Tz;
+use serde::Deserialize;
+
+use crate::api::{
+    AdhanError, DataSource, HijriDate, ParsedLocation, ParsedPrayerTimeItem,
+    ParsedPrayerTimesResponse, PrayerTimesPeriod, PrayerTimesProvider, PrayerTimesQuery,
+    REQUEST_TIMEOUT, ResponseCache, parse_timezone, today_in,
+};
+use crate::calc::{self, AsrSchool, CalculationMethod, HighLatitudeRule, IshaRule};
+
+pub const ALADHAN_BASE_URL: &str = "https://api.aladhan.com/v1";
+
+#[derive(Debug, Deserialize)]
+pub struct AladhanResponse<T> {
+    pub data: T,
+}
+
+/// Body of a failed request, where `data` holds the reason
+#[derive(Debug, Deserialize)]
+pub struct AladhanFailure {
+    pub code: u16,
+    pub data: String,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct AladhanDay {
+    pub timings: AladhanTimings,
+    pub date: AladhanDate,
+    pub meta: AladhanMeta,
+}
+
+/// Times requested with `iso8601=true`, e.g. `2026-10-17T05:31:00+01:00`
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "PascalCase")]
+pub struct AladhanTimings {
+    pub fajr: String,
+    pub sunrise: String,
+    pub dhuhr: String,
+    pub asr: String,
+    pub maghrib: String,
+    pub isha: String,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct AladhanDate {
+    pub gregorian: AladhanGregorianDate,
+    pub hijri: AladhanHijriDate,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct AladhanGregorianDate {
+    /// `DD-MM-YYYY`
+    pub date: String,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct AladhanHijriDate {
+    pub day: String,
+    pub month: AladhanHijriMonth,
+    pub year: String,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct AladhanHijriMonth {
+    pub en: String,
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct AladhanMeta {
+    pub latitude: f64,
+    pub longitude: f64,
+    pub timezone: String,
+    pub method: AladhanMethod,
+    pub latitude_adjustment_method: String,
+    pub school: String,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct AladhanMethod {
+    pub name: String,
+}
+
+impl CalculationMethod {
+    /// Method identifier understood by Aladhan, custom methods use 99 along
+    /// with [`CalculationMethod::aladhan_settings`]
+    pub fn aladhan_id(&self) -> u8 {
+        match self {
+            Self::Karachi => 1,
+            Self::Isna => 2,
+            Self::MuslimWorldLeague => 3,
+            Self::UmmAlQura => 4,
+            Self::Egyptian => 5,
+            Self::Tehran => 7,
+            Self::Gulf => 8,
+            Self::Kuwait => 9,
+            Self::Qatar => 10,
+            Self::Singapore => 11,
+            Self::Turkey => 13,
+            Self::Custom(_) => 99,
+        }
+    }
+
+    /// `methodSettings` parameter of a custom method
+    pub fn aladhan_settings(&self) -> Option<String> {
+        let Self::Custom(params) = self else {
+            return None;
+        };
+
+        let maghrib = match params.maghrib_angle {
+            Some(angle) => angle.to_string(),
+            None => "null".to_string(),
+        };
+        let isha = match params.isha {
+            IshaRule::Angle(angle) => angle.to_string(),
+            IshaRule::Interval(minutes) => format!("{minutes} min"),
+        };
+
+        Some(format!("{},{maghrib},{isha}", params.fajr_angle))
+    }
+}
+
+impl HighLatitudeRule {
+    /// `latitudeAdjustmentMethod` understood by Aladhan, which has no
+    /// nearest latitude rule
+    pub fn aladhan_id(&self) -> Option<u8> {
+        match self {
+            Self::MiddleOfTheNight => Some(1),
+            Self::OneSeventh => Some(2),
+            Self::AngleBased => Some(3),
+            Self::NearestLatitude => None,
+        }
+    }
+
+    fn from_aladhan(name: &str) -> Option<Self> {
+        match name {
+            "MIDDLE_OF_THE_NIGHT" => Some(Self::MiddleOfTheNight),
+            "ONE_SEVENTH" => Some(Self::OneSeventh),
+            "ANGLE_BASED" => Some(Self::AngleBased),
+            _ => None,
+        }
+    }
+}
+
+impl AladhanDay {
+    /// Wall clock time in `timezone` of an ISO 8601 time
+    fn parse_time(str_prayer_time: &str, timezone: &Tz) -> Result<NaiveTime, AdhanError> {
+        let instant = DateTime::parse_from_rfc3339(str_prayer_time)?;
+        Ok(instant.with_timezone(timezone).time())
+    }
+
+    pub fn parse(&self, timezone: &Tz) -> Result<ParsedPrayerTimeItem, AdhanError> {
+        let hijri = &self.date.hijri;
+        let times = [
+            Self::parse_time(&self.timings.fajr, timezone)?,
+            Self::parse_time(&self.timings.sunrise, timezone)?,
+            Self::parse_time(&self.timings.dhuhr, timezone)?,
+            Self::parse_time(&self.timings.asr, timezone)?,
+            Self::parse_time(&self.timings.maghrib, timezone)?,
+            Self::parse_time(&self.timings.isha, timezone)?,
+        ];
+
+        Ok(ParsedPrayerTimeItem::from_local_times(
+            timezone,
+            NaiveDate::parse_from_str(&self.date.gregorian.date, "%d-%m-%Y")?,
+            times,
+            Some(HijriDate {
+                day: hijri.day.parse().unwrap_or_default(),
+                month_name: hijri.month.en.clone(),
+                year: hijri.year.parse().unwrap_or_default(),
+            }),
+        ))
+    }
+}
+
+fn is_unknown_location(reason: &str) -> bool {
+    let reason = reason.to_lowercase();
+    reason.contains("unable to") || reason.contains("not found") || reason.contains("invalid")
+}
+
+/// Provider speaking the api.aladhan.com protocol, or any server
+/// compatible with it
+pub struct AladhanProvider {
+    base_url: String,
+    cache: ResponseCache,
+}
+
+impl AladhanProvider {
+    pub fn new(base_url: &str) -> Self {
+        AladhanProvider {
+            base_url: base_url.trim_end_matches('/').to_string(),
+            cache: ResponseCache::default(),
+        }
+    }
+
+    pub fn with_cache(self, cache: ResponseCache) -> Self {
+        AladhanProvider { cache, ..self }
+    }
+
+    /// Endpoint suffix and parameters locating the query: coordinates when
+    /// known, otherwise city and country when given as `London, UK`, or a
+    /// free form address
+    fn location_params(query: &PrayerTimesQuery) -> (&'static str, Vec<(&'static str, String)>) {
+        if let Some(coordinates) = &query.coordinates {
+            return (
+                "",
+                vec![
+                    ("latitude", coordinates.latitude.to_string()),
+                    ("longitude", coordinates.longitude.to_string()),
+                ],
+            );
+        }
+
+        match query.city.rsplit_once(',') {
+            Some((city, country)) => (
+                "ByCity",
+                vec![
+                    ("city", city.trim().to_string()),
+                    ("country", country.trim().to_string()),
+                ],
+            ),
+            None => ("ByAddress", vec![("address", query.city.clone())]),
+        }
+    }
+
+    fn settings_params(query: &PrayerTimesQuery) -> Vec<(&'static str, String)> {
+        let mut params = vec![("iso8601", "true".to_string())];
+
+        if let Some(method) = &query.method {
+            params.push(("method", method.aladhan_id().to_string()));
+
+            if let Some(settings) = method.aladhan_settings() {
+                params.push(("methodSettings", settings));
+            }
+        }
+
+        if let Some(asr_school) = query.asr_school {
+            let school = match asr_school {
+                AsrSchool::Shafii => "0",
+                AsrSchool::Hanafi => "1",
+            };
+            params.push(("school", school.to_string()));
+        }
+
+        if let Some(id) = query.high_latitude_rule.and_then(|rule| rule.aladhan_id()) {
+            params.push(("latitudeAdjustmentMethod", id.to_string()));
+        }
+
+        if let Some(timezone) = query.timezone {
+            params.push(("timezonestring", timezone.name().to_string()));
+        }
+
+        params
+    }
+
+    /// Data of the response to `path`, and whether it was fetched or cached
+    async fn get<T: serde::de::DeserializeOwned>(
+        &self,
+        path: &str,
+        params: &[(&'static str, String)],
+        query: &PrayerTimesQuery,
+    ) -> Result<(T, DataSource), AdhanError> {
+        let url = reqwest::Url::parse_with_params(&format!("{}/{path}", self.base_url), params)
+            .map_err(|error| AdhanError::ProviderFailure(error.to_string()))?;
+        let client = reqwest::Client::builder()
+            .timeout(REQUEST_TIMEOUT)
+            .build()?;
+
+        let check = |status: reqwest::StatusCode, response_text: &str| {
+            if status.is_success() {
+                return Ok(());
+            }
+
+            match serde_json::from_str::<AladhanFailure>(response_text) {
+                Ok(failure) if failure.code == 400 && is_unknown_location(&failure.data) => {
+                    Err(AdhanError::UnknownCity(query.city.clone()))
+                }
+                Ok(failure) => Err(AdhanError::ProviderFailure(failure.data)),
+                Err(_) => Err(AdhanError::HttpStatus(status)),
+            }
+        };
+        let (response_text, source) = self
+            .cache
+            .get(self.id(), &client, url, &query.period, check)
+            .await?;
+
+        let data: AladhanResponse<T> = serde_json::from_str(&response_text)?;
+        Ok((data.data, source))
+    }
+
+    async fn get_days(
+        &self,
+        query: &PrayerTimesQuery,
+    ) -> Result<(Vec<AladhanDay>, DataSource), AdhanError> {
+        let (endpoint, mut params) = Self::location_params(query);
+        params.extend(Self::settings_params(query));
+
+        let today = query
+            .date
+            .unwrap_or_else(|| today_in(query.timezone.as_ref()));
+        let dates = query.period.dates_from(today);
+
+        let (mut days, source) = match query.period {
+            PrayerTimesPeriod::Daily => {
+                let path = format!("timings{endpoint}/{}", today.format("%d-%m-%Y"));
+                let (day, source) = self.get::<AladhanDay>(&path, &params, query).await?;
+                (vec![day], source)
+            }
+            PrayerTimesPeriod::Yearly => {
+                let path = format!("calendar{endpoint}/{}", today.year());
+                let (months, source): (HashMap<String, Vec<AladhanDay>>, _) =
+                    self.get(&path, &params, query).await?;
+                (months.into_values().flatten().collect(), source)
+            }
+            PrayerTimesPeriod::Weekly | PrayerTimesPeriod::Monthly => {
+                let mut months: Vec<(i32, u32)> = dates
+                    .iter()
+                    .map(|date| (date.year(), date.month()))
+                    .collect();
+                months.dedup();
+
+                let mut days = Vec::new();
+                let mut source = DataSource::Live;
+                for (year, month) in months {
+                    let path = format!("calendar{endpoint}/{year}/{month}");
+                    let (month, month_source) =
+                        self.get::<Vec<AladhanDay>>(&path, &params, query).await?;
+                    days.extend(month);
+                    source = source.combine(month_source);
+                }
+                (days, source)
+            }
+        };
+
+        days.retain(|day| {
+            NaiveDate::parse_from_str(&day.date.gregorian.date, "%d-%m-%Y")
+                .is_ok_and(|date| dates.contains(&date))
+        });
+        days.sort_by_cached_key(|day| {
+            NaiveDate::parse_from_str(&day.date.gregorian.date, "%d-%m-%Y").ok()
+        });
+
+        Ok((days, source))
+    }
+}
+
+impl Default for AladhanProvider {
+    fn default() -> Self {
+        Self::new(ALADHAN_BASE_URL)
+    }
+}
+
+#[async_trait]
+impl PrayerTimesProvider for AladhanProvider {
+    fn id(&self) -> &'static str {
+        "aladhan"
+    }
+
+    fn name(&self) -> &'static str {
+        "Aladhan"
+    }
+
+    fn accepts_coordinates(&self) -> bool {
+        true
+    }
+
+    async fn get_prayer_times(
+        &self,
+        query: &PrayerTimesQuery,
+    ) -> Result<ParsedPrayerTimesResponse, AdhanError> {
+        let (days, source) = self.get_days(query).await?;
+        let first = days.first().ok_or(AdhanError::EmptyResponse)?;
+        let meta = &first.meta;
+
+        let (city, country) = match query.city.rsplit_once(',') {
+            Some((city, country)) => (city.trim(), country.trim()),
+            None => (query.city.as_str(), ""),
+        };
+
+        let timezone = match query.timezone {
+            Some(timezone) => timezone,
+            None => parse_timezone(&meta.timezone)?,
+        };
+
+        let location = ParsedLocation {
+            state: String::new(),
+            city: city.to_string(),
+            country: country.to_string(),
+            timezone,
+            country_code: String::new(),
+            qibla_direction: calc::qibla_direction(meta.latitude, meta.longitude),
+            latitude: meta.latitude,
+            longitude: meta.longitude,
+        };
+
+        let asr_school = match meta.school.as_str() {
+            "HANAFI" => AsrSchool::Hanafi,
+            _ => AsrSchool::Shafii,
+        };
+
+        Ok(ParsedPrayerTimesResponse {
+            location,
+            items: days
+                .iter()
+                .map(|day| day.parse(&timezone))
+                .collect::<Result<_, _>>()?,
+            period: query.period.clone(),
+            method_name: meta.method.name.clone(),
+            asr_school,
+            high_latitude_rule: HighLatitudeRule::from_aladhan(&meta.latitude_adjustment_method),
+            source,
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::{
+        io::{Read, Write},
+        net::TcpListener,
+        sync::{Arc, Mutex},
+        thread,
+    };
+
+    use chrono::Days;
+
+    use super::*;
+
+    /// Serves each request with the status and body `respond` gives for its
+    /// path and query, and records the requests it received
+    fn serve(respond: fn(&str) -> (u16, String)) -> (String, Arc<Mutex<Vec<String>>>) {
+        let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+        let base_url = format!("http://{}/v1", listener.local_addr().unwrap());
+        let requests = Arc::new(Mutex::new(Vec::new()));
+
+        let received = requests.clone();
+        thread::spawn(move || {
+            for stream in listener.incoming() {
+                let mut stream = stream.unwrap();
+                let mut request = Vec::new();
+                let mut buffer = [0; 1024];
+                while !request.ends_with(b"\r\n\r\n") {
+                    let read = stream.read(&mut buffer).unwrap();
+                    if read == 0 {
+                        break;
+                    }
+                    request.extend_from_slice(&buffer[..read]);
+                }
+
+                let request = String::from_utf8_lossy(&request);
+                let target = request.split(' ').nth(1).unwrap_or_default().to_string();
+                let (status, body) = respond(target.trim_start_matches("/v1/"));
+                received.lock().unwrap().push(target);
+
+                write!(
+                    stream,
+                    "HTTP/1.1 {status} Canned\r\nContent-Type: application/json\r\n\
+                     Content-Length: {}\r\nConnection: close\r\n\r\n{body}",
+                    body.len()
+                )
+                .unwrap();
+            }
+        });
+
+        (base_url, requests)
+    }
+
+    fn query(city: &str, period: PrayerTimesPeriod) -> PrayerTimesQuery {
+        PrayerTimesQuery {
+            city: city.to_string(),
+            coordinates: None,
+            period,
+            date: None,
+            method: Some(CalculationMethod::MuslimWorldLeague),
+            asr_school: None,
+            high_latitude_rule: None,
+            timezone: Some(Tz::UTC),
+            location: None,
+        }
+    }
+
+    fn today() -> NaiveDate {
+        today_in(Some(&Tz::UTC))
+    }
+
+    fn day(date: NaiveDate) -> String {
+        let time = |hour: u32, minute: u32| {
+            format!("{}T{hour:02}:{minute:02}:00+00:00", date.format("%Y-%m-%d"))
+        };
+
+        format!(
+            r#"{{
+                "timings": {{
+                    "Fajr": "{}", "Sunrise": "{}", "Dhuhr": "{}",
+                    "Asr": "{}", "Sunset": "{}", "Maghrib": "{}",
+                    "Isha": "{}", "Imsak": "{}", "Midnight": "{}"
+                }},
+                "date": {{
+                    "readable": "",
+                    "gregorian": {{ "date": "{}", "format": "DD-MM-YYYY" }},
+                    "hijri": {{ "day": "25", "month": {{ "number": 4, "en": "Rabīʿ al-thānī" }}, "year": "1448" }}
+                }},
+                "meta": {{
+                    "latitude": 51.5073509,
+                    "longitude": -0.1277583,
+                    "timezone": "Europe/London",
+                    "method": {{ "id": 3, "name": "Muslim World League" }},
+                    "latitudeAdjustmentMethod": "ANGLE_BASED",
+                    "midnightMode": "STANDARD",
+                    "school": "STANDARD"
+                }}
+            }}"#,
+            time(5, 31),
+            time(7, 20),
+            time(12, 44),
+            time(15, 35),
+            time(18, 8),
+            time(18, 8),
+            time(19, 46),
+            time(5, 21),
+            time(0, 44),
+            date.format("%d-%m-%Y"),
+        )
+    }
+
+    fn success(data: String) -> (u16, String) {
+        (
+            200,
+            format!(r#"{{"code":200,"status":"OK","data":{data}}}"#),
+        )
+    }
+
+    #[tokio::test]
+    async fn parses_the_timings_of_a_city() {
+        let (base_url, requests) = serve(|target| {
+            if target.starts_with("timingsByCity/") {
+                success(day(today()))
+            } else {
+                (404, String::new())
+            }
+        });
+
+        let response = AladhanProvider::new(&base_url)
+            .get_prayer_times(&query("London, UK", PrayerTimesPeriod::Daily))
+            .await
+            .unwrap();
+
+        let request = &requests.lock().unwrap()[0];
+        assert!(request.starts_with(&format!(
+            "/v1/timingsByCity/{}?",
+            today().format("%d-%m-%Y")
+        )));
+        assert!(request.contains("city=London&country=UK"));
+        assert!(request.contains("method=3"));
+
+        assert_eq!(response.location.city, "London");
+        assert_eq!(response.location.country, "UK");
+        assert_eq!(response.method_name, "Muslim World League");
+        assert_eq!(
+            response.high_latitude_rule,
+            Some(HighLatitudeRule::AngleBased)
+        );
+        assert_eq!(response.asr_school, AsrSchool::Shafii);
+
+        let item = &response.items[0];
+        assert_eq!(item.date, today());
+        assert_eq!(item.fajr.time(), NaiveTime::from_hms_opt(5, 31, 0).unwrap());
+        assert_eq!(
+            item.isha.time(),
+            NaiveTime::from_hms_opt(19, 46, 0).unwrap()
+        );
+        assert_eq!(item.hijri.as_ref().unwrap().year, 1448);
+    }
+
+    #[tokio::test]
+    async fn keeps_the_days_of_the_week_from_the_calendars() {
+        let (base_url, _) = serve(|target| {
+            let Some(path) = target
+                .strip_prefix("calendarByAddress/")
+                .and_then(|path| path.split('?').next())
+            else {
+                return (404, String::new());
+            };
+            let (year, month) = path.split_once('/').unwrap();
+            let (year, month): (i32, u32) = (year.parse().unwrap(), month.parse().unwrap());
+
+            // The whole month, as Aladhan sends it
+            let first = NaiveDate::from_ymd_opt(year, month, 1).unwrap();
+            let days: Vec<String> = first
+                .iter_days()
+                .take_while(|date| date.month() == month)
+                .map(day)
+                .collect();
+            success(format!("[{}]", days.join(",")))
+        });
+
+        let response = AladhanProvider::new(&base_url)
+            .get_prayer_times(&query("Makkah", PrayerTimesPeriod::Weekly))
+            .await
+            .unwrap();
+
+        let dates: Vec<NaiveDate> = response.items.iter().map(|item| item.date).collect();
+        assert_eq!(dates, PrayerTimesPeriod::Weekly.dates_from(today()));
+        assert!(dates.contains(&(today() + Days::new(6))));
+    }
+
+    #[tokio::test]
+    async fn maps_an_unknown_location_to_unknown_city() {
+        let (base_url, _) = serve(|_| {
+            (
+                400,
+                r#"{"code":400,"status":"BAD_REQUEST","data":"Unable to find the location."}"#
+                    .to_string(),
+            )
+        });
+
+        let error = AladhanProvider::new(&base_url)
+            .get_prayer_times(&query("Atlantis", PrayerTimesPeriod::Daily))
+            .await
+            .err()
+            .unwrap();
+
+        assert!(matches!(error, AdhanError::UnknownCity(city) if city == "Atlantis"));
+    }
+
+    #[tokio::test]
+    async fn maps_other_failures_to_provider_failure() {
+        let (base_url, _) = serve(|_| {
+            (
+                429,
+                r#"{"code":429,"status":"TOO_MANY_REQUESTS","data":"Rate limit exceeded."}"#
+                    .to_string(),
+            )
+        });
+
+        let error = AladhanProvider::new(&base_url)
+            .get_prayer_times(&query("London, UK", PrayerTimesPeriod::Daily))
+            .await
+            .err()
+            .unwrap();
+
+        assert!(
+            matches!(error, AdhanError::ProviderFailure(reason) if reason == "Rate limit exceeded.")
+        );
+    }
+}
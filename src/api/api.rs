@@ -1,31 +1,118 @@
+use async_trait::async_trait;
+use chrono::NaiveDate;
+use reqwest::Url;
+
 use crate::api::error::AdhanError;
 use crate::api::responses::PrayerTimesPeriod;
+use crate::calc::{AsrSchool, CalculationMethod};
+
+use super::cache::{DataSource, ResponseCache};
+use super::parsed::ParsedPrayerTimesResponse;
+use super::provider::{PrayerTimesProvider, PrayerTimesQuery, REQUEST_TIMEOUT};
+use super::responses::{PrayerTimesResponse, PrayerTimesStatus};
+
+pub const MUSLIMSALAT_BASE_URL: &str = "https://muslimsalat.com";
+
+#[derive(Default)]
+pub struct MuslimSalatProvider {
+    cache: ResponseCache,
+}
+
+impl MuslimSalatProvider {
+    pub fn with_cache(cache: ResponseCache) -> Self {
+        MuslimSalatProvider { cache }
+    }
+}
+
+#[async_trait]
+impl PrayerTimesProvider for MuslimSalatProvider {
+    fn id(&self) -> &'static str {
+        "muslimsalat"
+    }
+
+    fn name(&self) -> &'static str {
+        "MuslimSalat"
+    }
 
-use super::responses::PrayerTimesResponse;
-use regex;
-use reqwest;
+    async fn get_prayer_times(
+        &self,
+        query: &PrayerTimesQuery,
+    ) -> Result<ParsedPrayerTimesResponse, AdhanError> {
+        let (data, source) = get_prayer_data_by_city(
+            &query.city,
+            query.period.clone(),
+            query.date,
+            query.method.as_ref(),
+            query.asr_school,
+            &self.cache,
+        )
+        .await?;
 
+        let mut parsed = data.parse(query.timezone)?;
+        parsed.source = source;
+        Ok(parsed)
+    }
+}
 
 pub async fn get_prayer_data_by_city(
     city: &str,
     period: PrayerTimesPeriod,
-) -> Result<PrayerTimesResponse, AdhanError> {
-    let url = format!("https://muslimsalat.com/{city}/{period}.json");
-    println!("URL={}", url);
+    date: Option<NaiveDate>,
+    method: Option<&CalculationMethod>,
+    asr_school: Option<AsrSchool>,
+    cache: &ResponseCache,
+) -> Result<(PrayerTimesResponse, DataSource), AdhanError> {
+    let method = match (method, asr_school) {
+        (None, Some(AsrSchool::Hanafi)) => Some(&CalculationMethod::Karachi),
+        _ => method,
+    };
 
-    let client= reqwest::Client::builder().user_agent(
-        "Mozilla/5.0 (Windows NT 10.0; Win64; x64) AppleWebKit/537.36 (KHTML, like Gecko) Chrome/137.0.0.0 Safari/537.36"
-    ).build()?;
+    let date = date
+        .unwrap_or_else(|| chrono::Local::now().date_naive())
+        .format("%d-%m-%Y")
+        .to_string();
 
-    let response = client.get(url).send().await?;
+    // Pushed as path segments so that spaces, slashes and non-ASCII letters
+    // in the city are percent-encoded
+    let mut url = Url::parse(MUSLIMSALAT_BASE_URL).expect("valid base URL");
+    {
+        let mut segments = url.path_segments_mut().expect("base URL with a path");
+        segments.push(city.trim());
+        match method {
+            Some(method) => {
+                let id = method
+                    .muslimsalat_id(asr_school.unwrap_or_default())
+                    .ok_or(AdhanError::UnsupportedMethod(*method))?;
+                segments
+                    .push(&period.to_string())
+                    .push(&date)
+                    .push("true")
+                    .push(&format!("{id}.json"));
+            }
+            None => {
+                segments
+                    .push(&period.to_string())
+                    .push(&format!("{date}.json"));
+            }
+        }
+    }
 
-    let response_text = response.text().await?;
+    let client = reqwest::Client::builder().user_agent(
+        "Mozilla/5.0 (Windows NT 10.0; Win64; x64) AppleWebKit/537.36 (KHTML, like Gecko) Chrome/137.0.0.0 Safari/537.36"
+    ).timeout(REQUEST_TIMEOUT).build()?;
 
-    let regex_m = regex::Regex::new(r#""(-?\d+(?:\.\d+)?)""#).unwrap(); // fix problem of api response types
+    let check = |status: reqwest::StatusCode, response_text: &str| {
+        if !status.is_success() {
+            return Err(AdhanError::HttpStatus(status));
+        }
 
-    let response_text = regex_m.replace_all(&response_text, "$1");
+        let status: PrayerTimesStatus = serde_json::from_str(response_text)?;
+        status.check(city)
+    };
+    let (response_text, source) = cache
+        .get("muslimsalat", &client, url, &period, check)
+        .await?;
 
-    println!("{response_text}");
-    let data: PrayerTimesResponse = serde_json::from_str(&response_text).unwrap();
-    Ok(data)
+    let data: PrayerTimesResponse = serde_json::from_str(&response_text)?;
+    Ok((data, source))
 }
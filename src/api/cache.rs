@@ -0,0 +1,429 @@
+use std::{fs, path::PathBuf};
+
+use chrono::{DateTime, Datelike, Local, TimeDelta, Utc};
+use reqwest::{Client, StatusCode, Url};
+use serde::{Deserialize, Serialize};
+
+use crate::api::{AdhanError, PrayerTimesPeriod};
+
+/// Where the data shown comes from
+#[derive(Debug, Clone, Copy, PartialEq, Default, Serialize, Deserialize)]
+pub enum DataSource {
+    /// Just fetched from the provider
+    #[default]
+    Live,
+    /// Served from the cache while still fresh
+    Cached(DateTime<Utc>),
+    /// Served from an outdated cache entry because the provider could not
+    /// be reached
+    Stale(DateTime<Utc>),
+}
+
+impl DataSource {
+    /// Source of data put together from two responses: the stalest of them,
+    /// and the oldest fetch when they are equally stale
+    pub fn combine(self, other: DataSource) -> DataSource {
+        match (self, other) {
+            (Self::Stale(a), Self::Stale(b)) => Self::Stale(a.min(b)),
+            (Self::Stale(at), _) | (_, Self::Stale(at)) => Self::Stale(at),
+            (Self::Cached(a), Self::Cached(b)) => Self::Cached(a.min(b)),
+            (Self::Cached(at), _) | (_, Self::Cached(at)) => Self::Cached(at),
+            (Self::Live, Self::Live) => Self::Live,
+        }
+    }
+}
+
+impl PrayerTimesPeriod {
+    /// How long a cached response of this period is served without asking
+    /// the provider again
+    pub fn cache_ttl(&self) -> TimeDelta {
+        match self {
+            Self::Daily | Self::Weekly => TimeDelta::days(1),
+            Self::Monthly => TimeDelta::days(7),
+            Self::Yearly => TimeDelta::days(30),
+        }
+    }
+}
+
+/// Response body as the provider sent it
+#[derive(Serialize, Deserialize)]
+struct CacheEntry {
+    url: String,
+    fetched_at: DateTime<Utc>,
+    body: String,
+}
+
+impl CacheEntry {
+    /// Whether the entry is recent enough at `now` and still answers for the
+    /// same day, month or year, some providers using the same address for
+    /// the times of every day
+    fn is_fresh(&self, period: &PrayerTimesPeriod, now: DateTime<Utc>) -> bool {
+        let fetched = self.fetched_at.with_timezone(&Local).date_naive();
+        let today = now.with_timezone(&Local).date_naive();
+        let same_window = match period {
+            PrayerTimesPeriod::Daily | PrayerTimesPeriod::Weekly => fetched == today,
+            PrayerTimesPeriod::Monthly => {
+                (fetched.year(), fetched.month()) == (today.year(), today.month())
+            }
+            PrayerTimesPeriod::Yearly => fetched.year() == today.year(),
+        };
+
+        same_window && now - self.fetched_at < period.cache_ttl()
+    }
+}
+
+/// Response bodies of the remote providers, kept under the user cache
+/// directory to avoid downloading them again and to keep working offline
+#[derive(Debug, Clone, Default)]
+pub struct ResponseCache {
+    /// No caching when `None`
+    dir: Option<PathBuf>,
+}
+
+impl ResponseCache {
+    pub fn new(dir: PathBuf) -> Self {
+        ResponseCache { dir: Some(dir) }
+    }
+
+    /// Cache in [`ResponseCache::default_dir`], or none without a cache
+    /// directory
+    pub fn user() -> Self {
+        Self::default_dir().map(Self::new).unwrap_or_default()
+    }
+
+    /// `$XDG_CACHE_HOME/adhan-cli` or the platform equivalent
+    pub fn default_dir() -> Option<PathBuf> {
+        dirs::cache_dir().map(|dir| dir.join("adhan-cli"))
+    }
+
+    /// File of the response of `provider` to `url`, the address holding
+    /// every parameter of the request
+    fn path(&self, provider: &str, url: &Url) -> Option<PathBuf> {
+        let dir = self.dir.as_ref()?;
+
+        Some(dir.join(format!("{provider}-{:016x}.json", fnv1a(url.as_str()))))
+    }
+
+    fn read(&self, provider: &str, url: &Url) -> Option<CacheEntry> {
+        let content = fs::read_to_string(self.path(provider, url)?).ok()?;
+        let entry: CacheEntry = serde_json::from_str(&content).ok()?;
+
+        // Two addresses with the same hash do not share their entry
+        (entry.url == url.as_str()).then_some(entry)
+    }
+
+    fn write(&self, provider: &str, url: &Url, body: &str) {
+        let (Some(dir), Some(path)) = (&self.dir, self.path(provider, url)) else {
+            return;
+        };
+        let entry = CacheEntry {
+            url: url.to_string(),
+            fetched_at: Utc::now(),
+            body: body.to_string(),
+        };
+
+        // The cache is only an optimisation, failing to write it is not an error
+        if let Ok(content) = serde_json::to_string(&entry) {
+            let _ = fs::create_dir_all(dir);
+            let _ = fs::write(path, content);
+        }
+    }
+
+    /// Body of the response of `provider` to a GET of `url` for the times
+    /// of `period`, served from the cache while fresh. `check` turns a
+    /// response into the error it reports, only the bodies it accepts being
+    /// cached. An outdated entry is served when the provider cannot be
+    /// reached
+    pub async fn get(
+        &self,
+        provider: &str,
+        client: &Client,
+        url: Url,
+        period: &PrayerTimesPeriod,
+        check: impl Fn(StatusCode, &str) -> Result<(), AdhanError>,
+    ) -> Result<(String, DataSource), AdhanError> {
+        let cached = self.read(provider, &url);
+
+        if let Some(entry) = &cached
+            && entry.is_fresh(period, Utc::now())
+        {
+            return Ok((entry.body.clone(), DataSource::Cached(entry.fetched_at)));
+        }
+
+        let response = async {
+            let response = client.get(url.clone()).send().await?;
+            let status = response.status();
+            Ok::<_, reqwest::Error>((status, response.text().await?))
+        };
+
+        match response.await {
+            Ok((status, body)) => {
+                check(status, &body)?;
+                self.write(provider, &url, &body);
+                Ok((body, DataSource::Live))
+            }
+            Err(error) if error.is_connect() || error.is_timeout() => match cached {
+                Some(entry) => Ok((entry.body, DataSource::Stale(entry.fetched_at))),
+                None => Err(error.into()),
+            },
+            Err(error) => Err(error.into()),
+        }
+    }
+}
+
+/// 64-bit FNV-1a hash, which unlike the standard hasher stays the same
+/// across Rust versions
+fn fnv1a(text: &str) -> u64 {
+    text.bytes().fold(0xcbf2_9ce4_8422_2325, |hash, byte| {
+        (hash ^ u64::from(byte)).wrapping_mul(0x0100_0000_01b3)
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use std::{
+        io::{Read, Write},
+        net::TcpListener,
+        sync::{
+            Arc,
+            atomic::{AtomicUsize, Ordering},
+        },
+        thread,
+    };
+
+    use chrono::NaiveDate;
+
+    use super::*;
+
+    /// Answers every request with `status` and `body`, counting them
+    fn serve(status: u16, body: &'static str) -> (String, Arc<AtomicUsize>) {
+        let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+        let base_url = format!("http://{}", listener.local_addr().unwrap());
+        let requests = Arc::new(AtomicUsize::new(0));
+
+        let received = requests.clone();
+        thread::spawn(move || {
+            for stream in listener.incoming() {
+                let mut stream = stream.unwrap();
+                let mut request = Vec::new();
+                let mut buffer = [0; 1024];
+                while !request.ends_with(b"\r\n\r\n") {
+                    let read = stream.read(&mut buffer).unwrap();
+                    if read == 0 {
+                        break;
+                    }
+                    request.extend_from_slice(&buffer[..read]);
+                }
+                received.fetch_add(1, Ordering::SeqCst);
+
+                write!(
+                    stream,
+                    "HTTP/1.1 {status} Canned\r\nContent-Length: {}\r\n\
+                     Connection: close\r\n\r\n{body}",
+                    body.len()
+                )
+                .unwrap();
+            }
+        });
+
+        (base_url, requests)
+    }
+
+    /// Address nothing listens on
+    fn unreachable() -> String {
+        let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+        format!("http://{}", listener.local_addr().unwrap())
+    }
+
+    fn cache(name: &str) -> ResponseCache {
+        let dir = std::env::temp_dir().join(format!("adhan-cli-{}-{name}", std::process::id()));
+        let _ = fs::remove_dir_all(&dir);
+        ResponseCache::new(dir)
+    }
+
+    fn url(base_url: &str, query: &str) -> Url {
+        Url::parse(&format!("{base_url}/timings?{query}")).unwrap()
+    }
+
+    fn accept(status: StatusCode, _: &str) -> Result<(), AdhanError> {
+        match status.is_success() {
+            true => Ok(()),
+            false => Err(AdhanError::HttpStatus(status)),
+        }
+    }
+
+    /// Stores `body` for `url` as fetched `age` ago
+    fn store(cache: &ResponseCache, url: &Url, body: &str, age: TimeDelta) -> DateTime<Utc> {
+        let fetched_at = Utc::now() - age;
+        let entry = CacheEntry {
+            url: url.to_string(),
+            fetched_at,
+            body: body.to_string(),
+        };
+        fs::create_dir_all(cache.dir.as_ref().unwrap()).unwrap();
+        fs::write(
+            cache.path("test", url).unwrap(),
+            serde_json::to_string(&entry).unwrap(),
+        )
+        .unwrap();
+
+        fetched_at
+    }
+
+    #[tokio::test]
+    async fn serves_fresh_entries() {
+        let cache = cache("fresh");
+        let (base_url, requests) = serve(200, "live");
+        let url = url(&base_url, "method=3");
+        let client = Client::new();
+
+        let (body, source) = cache
+            .get(
+                "test",
+                &client,
+                url.clone(),
+                &PrayerTimesPeriod::Daily,
+                accept,
+            )
+            .await
+            .unwrap();
+        assert_eq!((body.as_str(), source), ("live", DataSource::Live));
+
+        let (body, source) = cache
+            .get("test", &client, url, &PrayerTimesPeriod::Daily, accept)
+            .await
+            .unwrap();
+        assert_eq!(body, "live");
+        assert!(matches!(source, DataSource::Cached(_)));
+        assert_eq!(requests.load(Ordering::SeqCst), 1);
+    }
+
+    #[tokio::test]
+    async fn fetches_expired_entries_again() {
+        let cache = cache("expired");
+        let (base_url, requests) = serve(200, "live");
+        let url = url(&base_url, "method=3");
+        store(&cache, &url, "old", TimeDelta::days(2));
+
+        let (body, source) = cache
+            .get(
+                "test",
+                &Client::new(),
+                url,
+                &PrayerTimesPeriod::Daily,
+                accept,
+            )
+            .await
+            .unwrap();
+
+        assert_eq!((body.as_str(), source), ("live", DataSource::Live));
+        assert_eq!(requests.load(Ordering::SeqCst), 1);
+    }
+
+    #[test]
+    fn expires_entries_of_another_window() {
+        let now = NaiveDate::from_ymd_opt(2026, 6, 15)
+            .unwrap()
+            .and_hms_opt(12, 0, 0)
+            .unwrap()
+            .and_local_timezone(Local)
+            .unwrap()
+            .with_timezone(&Utc);
+        let entry = |age: TimeDelta| CacheEntry {
+            url: String::new(),
+            fetched_at: now - age,
+            body: String::new(),
+        };
+
+        assert!(entry(TimeDelta::hours(1)).is_fresh(&PrayerTimesPeriod::Daily, now));
+        assert!(!entry(TimeDelta::hours(13)).is_fresh(&PrayerTimesPeriod::Daily, now));
+        assert!(entry(TimeDelta::hours(13)).is_fresh(&PrayerTimesPeriod::Yearly, now));
+        assert!(!entry(TimeDelta::days(8)).is_fresh(&PrayerTimesPeriod::Monthly, now));
+    }
+
+    #[tokio::test]
+    async fn falls_back_to_stale_entries_when_unreachable() {
+        let cache = cache("stale");
+        let url = url(&unreachable(), "method=3");
+        let fetched_at = store(&cache, &url, "old", TimeDelta::days(2));
+
+        let (body, source) = cache
+            .get(
+                "test",
+                &Client::new(),
+                url,
+                &PrayerTimesPeriod::Daily,
+                accept,
+            )
+            .await
+            .unwrap();
+
+        assert_eq!(body, "old");
+        assert_eq!(source, DataSource::Stale(fetched_at));
+    }
+
+    #[tokio::test]
+    async fn reports_provider_errors_despite_stale_entries() {
+        let cache = cache("failure");
+        let (base_url, _) = serve(500, "failure");
+        let url = url(&base_url, "method=3");
+        store(&cache, &url, "old", TimeDelta::days(2));
+
+        let error = cache
+            .get(
+                "test",
+                &Client::new(),
+                url.clone(),
+                &PrayerTimesPeriod::Daily,
+                accept,
+            )
+            .await
+            .unwrap_err();
+
+        assert!(matches!(error, AdhanError::HttpStatus(status) if status.as_u16() == 500));
+        assert_eq!(cache.read("test", &url).unwrap().body, "old");
+    }
+
+    #[tokio::test]
+    async fn keeps_requests_apart() {
+        let cache = cache("keys");
+        let (base_url, requests) = serve(200, "live");
+        let client = Client::new();
+        let period = PrayerTimesPeriod::Daily;
+
+        for query in [
+            "method=3",
+            "method=3&latitudeAdjustmentMethod=1",
+            "method=2",
+        ] {
+            let (_, source) = cache
+                .get("test", &client, url(&base_url, query), &period, accept)
+                .await
+                .unwrap();
+            assert_eq!(source, DataSource::Live);
+        }
+        assert_eq!(requests.load(Ordering::SeqCst), 3);
+
+        let url = url(&base_url, "method=3");
+        assert_ne!(cache.path("test", &url), cache.path("other", &url));
+    }
+
+    #[test]
+    fn combines_sources() {
+        let earlier = Utc::now() - TimeDelta::hours(2);
+        let later = Utc::now();
+
+        assert_eq!(
+            DataSource::Live.combine(DataSource::Cached(later)),
+            DataSource::Cached(later)
+        );
+        assert_eq!(
+            DataSource::Cached(earlier).combine(DataSource::Stale(later)),
+            DataSource::Stale(later)
+        );
+        assert_eq!(
+            DataSource::Cached(later).combine(DataSource::Cached(earlier)),
+            DataSource::Cached(earlier)
+        );
+    }
+}
@@ -1,18 +1,180 @@
 use std::fmt;
 
+use crate::{
+    api::{MAX_OFFSET_MINUTES, Prayer},
+    calc::CalculationMethod,
+};
+
 #[derive(Debug)]
 pub enum AdhanError {
     Reqwest(reqwest::Error),
+    Json(serde_json::Error),
+    Io(std::io::Error),
+    Config(String),
+    DBus(zbus::Error),
+    #[cfg(feature = "audio")]
+    Audio(String),
+    HttpStatus(reqwest::StatusCode),
+    ProviderFailure(String),
+    UnknownCity(String),
+    AmbiguousCity(String, Vec<String>),
     ChronoParse(chrono::ParseError),
     InvalidPeriod,
+    InvalidMethod,
+    InvalidAsrSchool,
+    InvalidHighLatitudeRule,
+    InvalidPrayer,
+    InvalidTuning,
+    InvalidTimezone(String),
+    InvalidOutputFormat,
+    InvalidDuration,
+    InvalidEventKind,
+    InvalidHook,
+    /// Hook target and the option its event needs
+    HookNeverRuns(String, String),
+    InvalidTheme,
+    InvalidTimeFormat,
+    UnknownConfigKey(String),
+    UnknownProfile(String),
+    UnknownProvider(String, Vec<&'static str>),
+    CoordinatesRequired,
+    CoordinatesUnsupported(&'static str),
+    LocationRequired,
+    EmptyResponse,
+    TodayNotCovered,
+    UnsupportedMethod(CalculationMethod),
+    UnreachableSunAngle(Prayer),
 }
 
 impl fmt::Display for AdhanError {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
         match self {
             AdhanError::Reqwest(e) => write!(f, "Request error: {}", e),
+            AdhanError::Json(e) => write!(f, "Could not read the provider response: {}", e),
+            AdhanError::Io(e) => write!(f, "I/O error: {}", e),
+            AdhanError::Config(e) => write!(f, "Configuration error: {}", e),
+            AdhanError::DBus(e) => write!(f, "D-Bus error: {}", e),
+            #[cfg(feature = "audio")]
+            AdhanError::Audio(e) => write!(f, "Audio error: {}", e),
+            AdhanError::HttpStatus(status) => {
+                write!(f, "The provider answered with HTTP status {}", status)
+            }
+            AdhanError::ProviderFailure(description) => {
+                write!(
+                    f,
+                    "The provider could not give the prayer times: {}",
+                    description
+                )
+            }
+            AdhanError::UnknownCity(city) => write!(
+                f,
+                "Unknown city \"{}\", check its spelling or add the country as in \"London, United Kingdom\"",
+                city
+            ),
+            AdhanError::AmbiguousCity(query, candidates) => write!(
+                f,
+                "Several cities match \"{query}\": {}. Add the country as in \"{}\"",
+                candidates.join("; "),
+                candidates.first().map(String::as_str).unwrap_or_default()
+            ),
             AdhanError::ChronoParse(e) => write!(f, "Parse error: {}", e),
             AdhanError::InvalidPeriod => write!(f, "Invalid prayer times period"),
+            AdhanError::InvalidMethod => write!(
+                f,
+                "Invalid calculation method, expected a method name or custom:<fajr>,<isha> with angles between 0 and 90 degrees"
+            ),
+            AdhanError::InvalidHighLatitudeRule => write!(
+                f,
+                "Invalid high latitude rule, expected middle-of-the-night, one-seventh, angle-based or nearest-latitude"
+            ),
+            AdhanError::InvalidTimezone(name) => {
+                write!(
+                    f,
+                    "Unknown time zone {name}, expected an IANA name such as Europe/London"
+                )
+            }
+            AdhanError::InvalidOutputFormat => {
+                write!(f, "Invalid output format, expected json, csv, tsv or table")
+            }
+            AdhanError::InvalidDuration => {
+                write!(
+                    f,
+                    "Invalid event duration, expected minutes and <prayer>:<minutes> pairs such as 20,fajr:30"
+                )
+            }
+            AdhanError::InvalidEventKind => write!(
+                f,
+                "Invalid prayer event, expected pre-alert, at-time, iqamah or end-of-window"
+            ),
+            AdhanError::InvalidHook => {
+                write!(
+                    f,
+                    "Invalid hook, expected <event>[:<prayer>]=<command> such as at-time:fajr=\"playerctl pause\""
+                )
+            }
+            AdhanError::HookNeverRuns(target, requirement) => {
+                write!(f, "The {target} hook never runs without {requirement}")
+            }
+            AdhanError::InvalidTheme => {
+                write!(f, "Invalid theme, expected default, light or mono")
+            }
+            AdhanError::InvalidTimeFormat => {
+                write!(f, "Invalid time format, expected 12h or 24h")
+            }
+            AdhanError::UnknownConfigKey(key) => write!(
+                f,
+                "Unknown configuration key {key}, expected city, coordinates, lat, lon, elevation, timezone, period, provider, method, asr, high_latitude, tune, theme or time_format"
+            ),
+            AdhanError::UnknownProfile(name) => {
+                write!(f, "No profile named {name} in the configuration file")
+            }
+            AdhanError::InvalidPrayer => write!(f, "Invalid prayer name"),
+            AdhanError::InvalidTuning => {
+                write!(
+                    f,
+                    "Invalid tuning, expected <prayer>:<minutes> pairs such as fajr:+2 with at most {MAX_OFFSET_MINUTES} minutes either way"
+                )
+            }
+            AdhanError::UnknownProvider(id, available) => write!(
+                f,
+                "Unknown prayer times provider {id}, expected one of {}",
+                available.join(", ")
+            ),
+            AdhanError::CoordinatesRequired => {
+                write!(
+                    f,
+                    "This provider needs the coordinates of the location (--lat and --lon)"
+                )
+            }
+            AdhanError::CoordinatesUnsupported(provider) => write!(
+                f,
+                "{provider} only looks up cities by name, give a city or use --provider aladhan or offline with coordinates"
+            ),
+            AdhanError::LocationRequired => write!(
+                f,
+                "No location given, pass a city or --lat and --lon, or set one with config set city <city>"
+            ),
+            AdhanError::EmptyResponse => write!(f, "The provider returned no prayer times"),
+            AdhanError::TodayNotCovered => {
+                write!(f, "The prayer times do not cover today")
+            }
+            AdhanError::InvalidAsrSchool => {
+                write!(f, "Invalid Asr school, expected shafii or hanafi")
+            }
+            AdhanError::UnsupportedMethod(method) => {
+                write!(
+                    f,
+                    "The {} method is not supported by this provider",
+                    method.name()
+                )
+            }
+            AdhanError::UnreachableSunAngle(prayer) => {
+                write!(
+                    f,
+                    "The sun never reaches the {} angle at this location",
+                    prayer.name()
+                )
+            }
         }
     }
 }
@@ -25,6 +187,24 @@ impl From<reqwest::Error> for AdhanError {
     }
 }
 
+impl From<serde_json::Error> for AdhanError {
+    fn from(err: serde_json::Error) -> Self {
+        AdhanError::Json(err)
+    }
+}
+
+impl From<std::io::Error> for AdhanError {
+    fn from(err: std::io::Error) -> Self {
+        AdhanError::Io(err)
+    }
+}
+
+impl From<zbus::Error> for AdhanError {
+    fn from(err: zbus::Error) -> Self {
+        AdhanError::DBus(err)
+    }
+}
+
 impl From<chrono::ParseError> for AdhanError {
     fn from(err: chrono::ParseError) -> Self {
         AdhanError::ChronoParse(err)
@@ -0,0 +1,100 @@
+use std::{fmt::Display, str::FromStr};
+
+use serde::{Deserialize, Deserializer, de::Error};
+
+#[derive(Deserialize)]
+#[serde(untagged)]
+enum StringOrNumber<T> {
+    Number(T),
+    String(String),
+}
+
+/// Accepts a number given either as a JSON number or as a string such as
+/// `"36.75"`, as muslimsalat.com quotes most of its numbers
+pub fn number_from_string<'de, D, T>(deserializer: D) -> Result<T, D::Error>
+where
+    D: Deserializer<'de>,
+    T: FromStr + Deserialize<'de>,
+    T::Err: Display,
+{
+    match StringOrNumber::<T>::deserialize(deserializer)? {
+        StringOrNumber::Number(number) => Ok(number),
+        StringOrNumber::String(string) => string.trim().parse().map_err(D::Error::custom),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use serde::Deserialize;
+
+    use super::number_from_string;
+    use crate::api::responses::PrayerTimesResponse;
+
+    /// Trimmed answer of muslimsalat.com for Istanbul
+    const MUSLIMSALAT_BODY: &str = r#"{
+        "title": "",
+        "query": "istanbul",
+        "for": "daily",
+        "method": 5,
+        "prayer_method_name": "Muslim World League",
+        "daylight": "0",
+        "timezone": "3",
+        "map_image": "https://maps.google.com/maps/api/staticmap?center=41.008238,28.978359",
+        "sealevel": "37",
+        "today_weather": { "pressure": null, "temperature": null },
+        "link": "http://muslimsalat.com/istanbul",
+        "qibla_direction": "151.62",
+        "latitude": "41.008238",
+        "longitude": 28.978359,
+        "address": "",
+        "city": "Istanbul",
+        "state": "Istanbul",
+        "postal_code": "01000",
+        "country": "Turkey",
+        "country_code": "TR",
+        "items": [
+            {
+                "date_for": "2026-10-17",
+                "fajr": "6:11 am",
+                "shurooq": "7:35 am",
+                "dhuhr": "1:04 pm",
+                "asr": "4:10 pm",
+                "maghrib": "6:32 pm",
+                "isha": "7:51 pm"
+            }
+        ],
+        "status_valid": 1,
+        "status_code": "1",
+        "status_description": "Success."
+    }"#;
+
+    #[test]
+    fn decodes_a_muslimsalat_body() {
+        let response: PrayerTimesResponse = serde_json::from_str(MUSLIMSALAT_BODY).unwrap();
+
+        assert_eq!(response.method, 5);
+        assert_eq!(response.daylight, 0);
+        assert_eq!(response.timezone, 3.0);
+        assert_eq!(response.sealevel, 37);
+        assert_eq!(response.status_code, 1);
+        assert_eq!(response.qibla_direction, 151.62);
+        assert_eq!(response.latitude, 41.008238);
+        assert_eq!(response.longitude, 28.978359);
+        // Not a number to the provider, the leading zero must stay
+        assert_eq!(response.postal_code, "01000");
+    }
+
+    #[derive(Deserialize)]
+    struct Lenient {
+        #[serde(deserialize_with = "number_from_string")]
+        value: i32,
+    }
+
+    #[test]
+    fn trims_quoted_numbers_and_rejects_words() {
+        let lenient: Lenient = serde_json::from_str(r#"{ "value": " 42 " }"#).unwrap();
+        assert_eq!(lenient.value, 42);
+
+        assert!(serde_json::from_str::<Lenient>(r#"{ "value": "forty-two" }"#).is_err());
+    }
+}
@@ -1,9 +1,21 @@
+mod aladhan;
+#[allow(clippy::module_inception)]
 mod api;
+mod cache;
+mod error;
+mod lenient;
 mod parsed;
+mod provider;
 mod responses;
-mod error;
+mod timezone;
+mod tuning;
 
+pub use aladhan::*;
 pub use api::*;
-pub use responses::PrayerTimesPeriod;
+pub use cache::*;
 pub use error::*;
 pub use parsed::*;
+pub use provider::*;
+pub use responses::PrayerTimesPeriod;
+pub use timezone::*;
+pub use tuning::*;
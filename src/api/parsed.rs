@@ -1,38 +1,75 @@
-use std::{collections::HashMap, f64::consts::PI as F64_PI};
+use std::{collections::HashMap, f64::consts::PI as F64_PI, str::FromStr};
 
-use chrono::{NaiveDate, NaiveTime};
+use chrono::{DateTime, NaiveDate, NaiveTime, TimeDelta, Utc};
+use chrono_tz::Tz;
+use serde::{Deserialize, Serialize};
 
-use crate::api::responses::PrayerTimesPeriod;
+use crate::{
+    api::{
+        AdhanError, DataSource,
+        responses::PrayerTimesPeriod,
+        timezone::{localize, zoned},
+    },
+    calc::{AsrSchool, HighLatitudeRule, Qibla},
+};
 
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[allow(dead_code)]
 pub struct ParsedPrayerTimesResponse {
     pub location: ParsedLocation,
     pub items: Vec<ParsedPrayerTimeItem>,
     pub period: PrayerTimesPeriod,
+    pub method_name: String,
+    pub asr_school: AsrSchool,
+    /// Rule used for Fajr and Isha at high latitudes, `None` when the
+    /// provider does not report it
+    pub high_latitude_rule: Option<HighLatitudeRule>,
+    #[serde(skip)]
+    pub source: DataSource,
 }
 
-#[derive(Debug, Clone)]
-
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[allow(dead_code)]
 pub struct ParsedLocation {
     pub state: String,
-    pub city:String,
+    pub city: String,
     pub country: String,
-    pub timezone: i8,
-    pub daylight: bool,
+    pub timezone: Tz,
     pub country_code: String,
     pub qibla_direction: f64,
     pub latitude: f64,
     pub longitude: f64,
 }
 
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct ParsedPrayerTimeItem {
     pub date: NaiveDate,
-    pub fajr: NaiveTime,
-    pub shurooq: NaiveTime,
-    pub dhuhr: NaiveTime,
-    pub asr: NaiveTime,
-    pub maghrib: NaiveTime,
-    pub isha: NaiveTime,
+    #[serde(with = "zoned")]
+    pub fajr: DateTime<Tz>,
+    #[serde(with = "zoned")]
+    pub shurooq: DateTime<Tz>,
+    #[serde(with = "zoned")]
+    pub dhuhr: DateTime<Tz>,
+    #[serde(with = "zoned")]
+    pub asr: DateTime<Tz>,
+    #[serde(with = "zoned")]
+    pub maghrib: DateTime<Tz>,
+    #[serde(with = "zoned")]
+    pub isha: DateTime<Tz>,
+    pub hijri: Option<HijriDate>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct HijriDate {
+    pub day: u32,
+    pub month_name: String,
+    pub year: i32,
+}
+
+impl std::fmt::Display for HijriDate {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{} {} {} AH", self.day, self.month_name, self.year)
+    }
 }
 
 #[derive(Debug, Clone, PartialEq, Eq, Hash, PartialOrd, Ord)]
@@ -69,8 +106,122 @@ impl Prayer {
     }
 }
 
+impl FromStr for Prayer {
+    type Err = AdhanError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.to_lowercase().as_str() {
+            "fajr" => Ok(Prayer::Fajr),
+            "shurooq" | "sunrise" => Ok(Prayer::Shurooq),
+            "dhuhr" => Ok(Prayer::Dhuhr),
+            "asr" => Ok(Prayer::Asr),
+            "maghrib" => Ok(Prayer::Maghrib),
+            "isha" => Ok(Prayer::Isha),
+            _ => Err(AdhanError::InvalidPrayer),
+        }
+    }
+}
+
+impl ParsedPrayerTimesResponse {
+    /// Current time in the zone of the location
+    pub fn now(&self) -> DateTime<Tz> {
+        Utc::now().with_timezone(&self.location.timezone)
+    }
+
+    /// Index of the item dated with the day of `now`
+    pub fn today_index(&self, now: &DateTime<Tz>) -> Option<usize> {
+        let date = now.date_naive();
+        self.items.iter().position(|item| item.date == date)
+    }
+
+    /// The day before `today`, estimated from it when the data starts there
+    pub fn day_before(&self, today: usize) -> ParsedPrayerTimeItem {
+        match today.checked_sub(1) {
+            Some(yesterday) => self.items[yesterday].clone(),
+            None => self.items[today].shifted(-1),
+        }
+    }
+
+    /// The day after `today`, estimated from it when the data stops there
+    pub fn day_after(&self, today: usize) -> ParsedPrayerTimeItem {
+        match self.items.get(today + 1) {
+            Some(item) => item.clone(),
+            None => self.items[today].next_day_estimate(),
+        }
+    }
+
+    /// First prayer after `now`, tomorrow's Fajr once today's Isha is past.
+    /// Yesterday's Isha comes first when it falls after midnight and is
+    /// still ahead.
+    pub fn next_prayer(&self, today: usize, now: &DateTime<Tz>) -> (Prayer, DateTime<Tz>) {
+        let yesterday_isha = self.day_before(today).isha;
+        if *now < yesterday_isha {
+            return (Prayer::Isha, yesterday_isha);
+        }
+
+        let prayer_map = self.items[today].to_hash_map();
+
+        for prayer in Prayer::all_prayers() {
+            let prayer_time = prayer_map[&prayer];
+
+            if *now < prayer_time {
+                return (prayer, prayer_time);
+            }
+        }
+
+        (Prayer::Fajr, self.day_after(today).fajr)
+    }
+}
+
+/// Formats a countdown as `HH:MM:SS`, or `MM:SS` under an hour
+pub fn format_countdown(remaining: TimeDelta) -> String {
+    let diff_seconds = remaining.num_seconds().max(0);
+
+    let hours = diff_seconds / 3600;
+    let minutes = (diff_seconds % 3600) / 60;
+    let seconds = diff_seconds % 60;
+
+    if hours > 0 {
+        format!("{:02}:{:02}:{:02}", hours, minutes, seconds)
+    } else {
+        format!("{:02}:{:02}", minutes, seconds)
+    }
+}
+
 impl ParsedPrayerTimeItem {
-    pub fn get_prayer(&self, prayer: &Prayer) -> NaiveTime {
+    /// Builds an item from the wall clock times of `date` in `timezone`,
+    /// given in the order of [`Prayer::all_prayers`]. A time earlier than
+    /// the prayer before it, such as an Isha after midnight, is on the next day.
+    pub fn from_local_times(
+        timezone: &Tz,
+        date: NaiveDate,
+        times: [NaiveTime; 6],
+        hijri: Option<HijriDate>,
+    ) -> Self {
+        let mut day = date;
+        let mut previous = times[0];
+        let zoned = times.map(|time| {
+            if time < previous {
+                day += TimeDelta::days(1);
+            }
+            previous = time;
+            localize(timezone, day.and_time(time))
+        });
+        let [fajr, shurooq, dhuhr, asr, maghrib, isha] = zoned;
+
+        ParsedPrayerTimeItem {
+            date,
+            fajr,
+            shurooq,
+            dhuhr,
+            asr,
+            maghrib,
+            isha,
+            hijri,
+        }
+    }
+
+    pub fn get_prayer(&self, prayer: &Prayer) -> DateTime<Tz> {
         match prayer {
             Prayer::Fajr => self.fajr,
             Prayer::Shurooq => self.shurooq,
@@ -81,7 +232,18 @@ impl ParsedPrayerTimeItem {
         }
     }
 
-    pub fn to_hash_map(&self) -> HashMap<Prayer, NaiveTime> {
+    pub fn set_prayer(&mut self, prayer: &Prayer, time: DateTime<Tz>) {
+        match prayer {
+            Prayer::Fajr => self.fajr = time,
+            Prayer::Shurooq => self.shurooq = time,
+            Prayer::Dhuhr => self.dhuhr = time,
+            Prayer::Asr => self.asr = time,
+            Prayer::Maghrib => self.maghrib = time,
+            Prayer::Isha => self.isha = time,
+        }
+    }
+
+    pub fn to_hash_map(&self) -> HashMap<Prayer, DateTime<Tz>> {
         let mut map = HashMap::new();
 
         map.insert(Prayer::Fajr, self.fajr);
@@ -93,8 +255,50 @@ impl ParsedPrayerTimeItem {
 
         map
     }
+
+    /// Estimate of the following day, keeping the same wall clock times,
+    /// for when the data ends before the next prayer
+    pub fn next_day_estimate(&self) -> Self {
+        self.shifted(1)
+    }
+
+    /// The same wall clock times `days` days later, or earlier when negative
+    fn shifted(&self, days: i64) -> Self {
+        let timezone = self.fajr.timezone();
+        let shift =
+            |time: DateTime<Tz>| localize(&timezone, time.naive_local() + TimeDelta::days(days));
+
+        ParsedPrayerTimeItem {
+            date: self.date + TimeDelta::days(days),
+            fajr: shift(self.fajr),
+            shurooq: shift(self.shurooq),
+            dhuhr: shift(self.dhuhr),
+            asr: shift(self.asr),
+            maghrib: shift(self.maghrib),
+            isha: shift(self.isha),
+            hijri: None,
+        }
+    }
 }
 
+impl ParsedLocation {
+    /// City and country, leaving out the parts the provider did not give
+    pub fn place(&self) -> String {
+        [self.city.as_str(), self.country.as_str()]
+            .into_iter()
+            .filter(|part| !part.is_empty())
+            .collect::<Vec<_>>()
+            .join(", ")
+    }
+
+    /// Qibla computed from the coordinates, rather than the direction the
+    /// provider gave
+    pub fn qibla(&self) -> Qibla {
+        Qibla::at(self.latitude, self.longitude)
+    }
+}
+
+#[allow(dead_code)]
 impl ParsedLocation {
     fn longitude_to_tile(lon: f64, zoom: u32) -> u32 {
         let n = 2u32.pow(zoom) as f64;
@@ -110,8 +314,6 @@ impl ParsedLocation {
         y.floor() as u32
     }
     pub fn get_tile(&self) -> Option<String> {
-
-
         let longitude = self.longitude;
         let latitude = self.latitude;
 
@@ -126,3 +328,110 @@ impl ParsedLocation {
         Some(link)
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use chrono::TimeZone;
+
+    use super::*;
+
+    fn oslo_midsummer() -> ParsedPrayerTimesResponse {
+        let time = |text: &str| NaiveTime::parse_from_str(text, "%H:%M").unwrap();
+        let day = |date: NaiveDate, isha: &str| {
+            ParsedPrayerTimeItem::from_local_times(
+                &chrono_tz::Europe::Oslo,
+                date,
+                [
+                    time("02:21"),
+                    time("03:54"),
+                    time("13:19"),
+                    time("17:51"),
+                    time("22:44"),
+                    time(isha),
+                ],
+                None,
+            )
+        };
+        let first = NaiveDate::from_ymd_opt(2026, 6, 20).unwrap();
+
+        ParsedPrayerTimesResponse {
+            location: ParsedLocation {
+                state: String::new(),
+                city: "Oslo".to_string(),
+                country: "Norway".to_string(),
+                timezone: chrono_tz::Europe::Oslo,
+                country_code: "NO".to_string(),
+                qibla_direction: 138.4,
+                latitude: 59.9139,
+                longitude: 10.7522,
+            },
+            items: vec![
+                day(first, "00:12"),
+                day(first + TimeDelta::days(1), "00:12"),
+            ],
+            period: PrayerTimesPeriod::Weekly,
+            method_name: "Muslim World League".to_string(),
+            asr_school: AsrSchool::Shafii,
+            high_latitude_rule: Some(HighLatitudeRule::AngleBased),
+            source: DataSource::Live,
+        }
+    }
+
+    #[test]
+    fn finds_today_by_the_date_of_the_location() {
+        let response = oslo_midsummer();
+        // Still the 20th in UTC
+        let now = chrono::Utc
+            .with_ymd_and_hms(2026, 6, 20, 22, 30, 0)
+            .unwrap()
+            .with_timezone(&chrono_tz::Europe::Oslo);
+        assert_eq!(response.today_index(&now), Some(1));
+
+        let later = now + TimeDelta::days(1);
+        assert_eq!(response.today_index(&later), None);
+    }
+
+    #[test]
+    fn isha_after_midnight_is_still_next() {
+        let response = oslo_midsummer();
+        let now = chrono_tz::Europe::Oslo
+            .with_ymd_and_hms(2026, 6, 21, 0, 5, 0)
+            .unwrap();
+        let today = response.today_index(&now).unwrap();
+
+        let (prayer, time) = response.next_prayer(today, &now);
+        assert_eq!(prayer, Prayer::Isha);
+        assert_eq!(time, response.items[0].isha);
+    }
+
+    #[test]
+    fn isha_after_midnight_is_estimated_when_yesterday_is_missing() {
+        let mut response = oslo_midsummer();
+        response.items.remove(0);
+        let now = chrono_tz::Europe::Oslo
+            .with_ymd_and_hms(2026, 6, 21, 0, 5, 0)
+            .unwrap();
+
+        let (prayer, time) = response.next_prayer(0, &now);
+        assert_eq!(prayer, Prayer::Isha);
+        assert_eq!(
+            time,
+            chrono_tz::Europe::Oslo
+                .with_ymd_and_hms(2026, 6, 21, 0, 12, 0)
+                .unwrap()
+        );
+    }
+
+    #[test]
+    fn fajr_follows_yesterdays_isha() {
+        let response = oslo_midsummer();
+        let now = chrono_tz::Europe::Oslo
+            .with_ymd_and_hms(2026, 6, 21, 0, 30, 0)
+            .unwrap();
+        let today = response.today_index(&now).unwrap();
+
+        let (prayer, time) = response.next_prayer(today, &now);
+        assert_eq!(prayer, Prayer::Fajr);
+        assert_eq!(time, response.items[1].fajr);
+    }
+}
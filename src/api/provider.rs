@@ -0,0 +1,136 @@
+use std::{sync::Arc, time::Duration};
+
+use async_trait::async_trait;
+use chrono::NaiveDate;
+use chrono_tz::Tz;
+
+use crate::{
+    api::{
+        AdhanError, AladhanProvider, MuslimSalatProvider, ParsedLocation,
+        ParsedPrayerTimesResponse, PrayerTimesPeriod, ResponseCache, Tuning,
+    },
+    calc::{
+        AsrSchool, CalculationMethod, CalculationSettings, Coordinates, HighLatitudeRule,
+        OfflineProvider,
+    },
+};
+
+/// Longest wait for a remote provider before giving up, or serving the
+/// cached times
+pub const REQUEST_TIMEOUT: Duration = Duration::from_secs(15);
+
+/// What to look up, options left to `None` use the provider defaults
+#[derive(Debug, Clone)]
+pub struct PrayerTimesQuery {
+    pub city: String,
+    pub coordinates: Option<Coordinates>,
+    pub period: PrayerTimesPeriod,
+    /// Day the period is counted from, today at the location when `None`
+    pub date: Option<NaiveDate>,
+    pub method: Option<CalculationMethod>,
+    pub asr_school: Option<AsrSchool>,
+    pub high_latitude_rule: Option<HighLatitudeRule>,
+    /// IANA zone of the location, overriding the one the provider reports
+    pub timezone: Option<Tz>,
+    /// Location found in the bundled city list, naming the times computed
+    /// offline
+    pub location: Option<ParsedLocation>,
+}
+
+impl PrayerTimesQuery {
+    pub fn settings(&self) -> CalculationSettings {
+        CalculationSettings {
+            method: self.method.unwrap_or_default(),
+            asr_school: self.asr_school.unwrap_or_default(),
+            high_latitude_rule: self.high_latitude_rule.unwrap_or_default(),
+        }
+    }
+}
+
+/// A query along with the provider answering it and the tuning applied to
+/// the times it gives, to fetch them again later
+pub struct Lookup {
+    pub provider: Arc<dyn PrayerTimesProvider>,
+    pub query: PrayerTimesQuery,
+    pub tuning: Option<Tuning>,
+}
+
+impl Lookup {
+    pub async fn fetch(&self) -> Result<ParsedPrayerTimesResponse, AdhanError> {
+        let mut parsed = self.provider.get_prayer_times(&self.query).await?;
+
+        if let Some(tuning) = &self.tuning {
+            parsed.apply_tuning(tuning);
+        }
+
+        Ok(parsed)
+    }
+}
+
+#[async_trait]
+pub trait PrayerTimesProvider: Send + Sync {
+    /// Identifier used to select the provider from the command line
+    fn id(&self) -> &'static str;
+
+    fn name(&self) -> &'static str;
+
+    /// Whether the provider looks up `PrayerTimesQuery::coordinates`, the
+    /// city being looked up otherwise
+    fn accepts_coordinates(&self) -> bool {
+        false
+    }
+
+    /// Whether the provider looks `PrayerTimesQuery::city` up by name
+    fn accepts_names(&self) -> bool {
+        true
+    }
+
+    async fn get_prayer_times(
+        &self,
+        query: &PrayerTimesQuery,
+    ) -> Result<ParsedPrayerTimesResponse, AdhanError>;
+}
+
+pub struct ProviderRegistry {
+    providers: Vec<Arc<dyn PrayerTimesProvider>>,
+}
+
+impl ProviderRegistry {
+    pub fn new() -> Self {
+        ProviderRegistry {
+            providers: Vec::new(),
+        }
+    }
+
+    pub fn register(&mut self, provider: Arc<dyn PrayerTimesProvider>) {
+        self.providers.push(provider);
+    }
+
+    pub fn get(&self, id: &str) -> Result<Arc<dyn PrayerTimesProvider>, AdhanError> {
+        self.providers
+            .iter()
+            .find(|provider| provider.id() == id.to_lowercase())
+            .cloned()
+            .ok_or_else(|| AdhanError::UnknownProvider(id.to_string(), self.ids()))
+    }
+
+    pub fn ids(&self) -> Vec<&'static str> {
+        self.providers
+            .iter()
+            .map(|provider| provider.id())
+            .collect()
+    }
+}
+
+impl Default for ProviderRegistry {
+    fn default() -> Self {
+        let mut registry = Self::new();
+
+        let cache = ResponseCache::user();
+        registry.register(Arc::new(MuslimSalatProvider::with_cache(cache.clone())));
+        registry.register(Arc::new(AladhanProvider::default().with_cache(cache)));
+        registry.register(Arc::new(OfflineProvider));
+
+        registry
+    }
+}
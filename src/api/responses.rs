@@ -1,11 +1,16 @@
+use crate::api::DataSource;
 use crate::api::error::AdhanError;
-use chrono::{NaiveDate, NaiveTime};
+use crate::calc::AsrSchool;
+use chrono::{Datelike, NaiveDate, NaiveTime};
+use chrono_tz::Tz;
 use serde::{Deserialize, Serialize};
 use std::str::FromStr;
 
+use super::lenient::number_from_string;
 use super::parsed::{ParsedLocation, ParsedPrayerTimeItem, ParsedPrayerTimesResponse};
+use super::timezone::timezone_for_location;
 
-#[derive(Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
 pub enum PrayerTimesPeriod {
     Daily,
     Weekly,
@@ -26,6 +31,26 @@ impl std::fmt::Display for PrayerTimesPeriod {
     }
 }
 
+impl PrayerTimesPeriod {
+    /// Dates covered by the period when requested on `today`
+    pub fn dates_from(&self, today: NaiveDate) -> Vec<NaiveDate> {
+        let (first, last) = match self {
+            Self::Daily => (today, today),
+            Self::Weekly => (today, today + chrono::Days::new(6)),
+            Self::Monthly => {
+                let first = today.with_day(1).unwrap();
+                (first, first + chrono::Months::new(1) - chrono::Days::new(1))
+            }
+            Self::Yearly => (
+                NaiveDate::from_ymd_opt(today.year(), 1, 1).unwrap(),
+                NaiveDate::from_ymd_opt(today.year(), 12, 31).unwrap(),
+            ),
+        };
+
+        first.iter_days().take_while(|date| *date <= last).collect()
+    }
+}
+
 impl FromStr for PrayerTimesPeriod {
     type Err = AdhanError;
 
@@ -51,21 +76,57 @@ pub struct PrayerTimeItem {
     pub isha: String,
 }
 
+/// Status fields of a response, readable even when the rest of the payload
+/// is missing because the request failed
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct PrayerTimesStatus {
+    #[serde(default, deserialize_with = "number_from_string")]
+    pub status_valid: i32,
+    #[serde(default, deserialize_with = "number_from_string")]
+    pub status_code: i32,
+    #[serde(default)]
+    pub status_description: String,
+    #[serde(default)]
+    pub items: Vec<serde_json::Value>,
+}
+
+impl PrayerTimesStatus {
+    pub fn check(&self, city: &str) -> Result<(), AdhanError> {
+        if self.status_valid != 1 {
+            return Err(AdhanError::ProviderFailure(self.status_description.clone()));
+        }
+
+        if self.items.is_empty() {
+            return Err(AdhanError::UnknownCity(city.to_string()));
+        }
+
+        Ok(())
+    }
+}
+
 #[derive(Debug, Serialize, Deserialize, Clone)]
 pub struct PrayerTimesResponse {
     pub title: String,
     pub query: String,
     #[serde(rename = "for")]
     pub for_period: String,
+    #[serde(deserialize_with = "number_from_string")]
     pub method: i32,
     pub prayer_method_name: String,
+    #[serde(deserialize_with = "number_from_string")]
     pub daylight: i32,
-    pub timezone: i8,
+    /// Standard UTC offset in hours, fractional for zones such as India's
+    #[serde(deserialize_with = "number_from_string")]
+    pub timezone: f64,
     pub map_image: String,
+    #[serde(deserialize_with = "number_from_string")]
     pub sealevel: i32,
     pub link: String,
+    #[serde(deserialize_with = "number_from_string")]
     pub qibla_direction: f64,
+    #[serde(deserialize_with = "number_from_string")]
     pub latitude: f64,
+    #[serde(deserialize_with = "number_from_string")]
     pub longitude: f64,
     pub address: String,
     pub city: String,
@@ -74,35 +135,64 @@ pub struct PrayerTimesResponse {
     pub country: String,
     pub country_code: String,
     pub items: Vec<PrayerTimeItem>,
+    #[serde(deserialize_with = "number_from_string")]
     pub status_valid: i32,
+    #[serde(deserialize_with = "number_from_string")]
     pub status_code: i32,
     pub status_description: String,
 }
 
 impl PrayerTimesResponse {
-    pub fn parse(&self) -> Result<ParsedPrayerTimesResponse, AdhanError> {
+    /// Parses the response, with the times in `timezone` when given, or
+    /// otherwise in the zone found from the location and its UTC offset
+    pub fn parse(&self, timezone: Option<Tz>) -> Result<ParsedPrayerTimesResponse, AdhanError> {
+        let timezone = timezone.unwrap_or_else(|| self.timezone());
+
         Ok(ParsedPrayerTimesResponse {
-            items: self.parse_items()?,
-            location: self.parse_location(),
+            items: self.parse_items(&timezone)?,
+            location: self.parse_location(timezone),
             period: PrayerTimesPeriod::from_str(&self.for_period)?,
+            method_name: self.prayer_method_name.clone(),
+            asr_school: self.asr_school(),
+            high_latitude_rule: None,
+            source: DataSource::Live,
         })
     }
 
-    pub fn parse_items(&self) -> Result<Vec<ParsedPrayerTimeItem>, AdhanError> {
-        self.items.iter().map(|item| item.parse()).collect()
+    /// muslimsalat.com only computes the Hanafi Asr for its Karachi Hanafi
+    /// method
+    pub fn asr_school(&self) -> AsrSchool {
+        if self.method == 3 {
+            AsrSchool::Hanafi
+        } else {
+            AsrSchool::Shafii
+        }
     }
 
-    pub fn parse_location(&self) -> ParsedLocation {
+    pub fn timezone(&self) -> Tz {
+        let hours = self.timezone + self.daylight as f64;
+        timezone_for_location(
+            self.latitude,
+            self.longitude,
+            &self.country_code,
+            (hours * 3600.0).round() as i32,
+        )
+    }
+
+    pub fn parse_items(&self, timezone: &Tz) -> Result<Vec<ParsedPrayerTimeItem>, AdhanError> {
+        self.items.iter().map(|item| item.parse(timezone)).collect()
+    }
+
+    pub fn parse_location(&self, timezone: Tz) -> ParsedLocation {
         ParsedLocation {
             country: self.country.clone(),
             state: self.state.clone(),
-            city:self.city.clone(),
+            city: self.city.clone(),
             country_code: self.country_code.clone(),
             latitude: self.latitude,
             longitude: self.longitude,
             qibla_direction: self.qibla_direction,
-            timezone: self.timezone,
-            daylight: self.daylight == 1,
+            timezone,
         }
     }
 }
@@ -116,16 +206,22 @@ impl PrayerTimeItem {
         Ok(NaiveDate::parse_from_str(str_date, "%Y-%-m-%-d")?)
     }
 
-    pub fn parse(&self) -> Result<ParsedPrayerTimeItem, AdhanError> {
-        let parsed = ParsedPrayerTimeItem {
-            date: Self::parse_date(&self.date_for)?,
-            shurooq: Self::parse_prayer_time(&self.shurooq)?,
-            fajr: Self::parse_prayer_time(&self.fajr)?,
-            dhuhr: Self::parse_prayer_time(&self.dhuhr)?,
-            asr: Self::parse_prayer_time(&self.asr)?,
-            maghrib: Self::parse_prayer_time(&self.maghrib)?,
-            isha: Self::parse_prayer_time(&self.isha)?,
-        };
+    pub fn parse(&self, timezone: &Tz) -> Result<ParsedPrayerTimeItem, AdhanError> {
+        let times = [
+            Self::parse_prayer_time(&self.fajr)?,
+            Self::parse_prayer_time(&self.shurooq)?,
+            Self::parse_prayer_time(&self.dhuhr)?,
+            Self::parse_prayer_time(&self.asr)?,
+            Self::parse_prayer_time(&self.maghrib)?,
+            Self::parse_prayer_time(&self.isha)?,
+        ];
+
+        let parsed = ParsedPrayerTimeItem::from_local_times(
+            timezone,
+            Self::parse_date(&self.date_for)?,
+            times,
+            None,
+        );
 
         Ok(parsed)
     }
@@ -0,0 +1,144 @@
+use chrono::{
+    DateTime, Local, LocalResult, NaiveDate, NaiveDateTime, Offset, TimeDelta, TimeZone, Utc,
+};
+use chrono_tz::Tz;
+use serde::{Deserialize, Deserializer, Serializer, de::Error};
+
+use crate::{api::AdhanError, calc::great_circle_distance, gazetteer::City};
+
+/// Farthest a bundled city may be, in kilometers, for a point to take its
+/// zone
+const NEAREST_CITY_RADIUS: f64 = 500.0;
+
+pub fn parse_timezone(name: &str) -> Result<Tz, AdhanError> {
+    name.parse()
+        .map_err(|_| AdhanError::InvalidTimezone(name.to_string()))
+}
+
+/// IANA zone of the machine, if it can be found
+pub fn system_timezone() -> Option<Tz> {
+    iana_time_zone::get_timezone().ok()?.parse().ok()
+}
+
+/// Current date in `timezone`, or on the machine when it is not known yet
+pub fn today_in(timezone: Option<&Tz>) -> NaiveDate {
+    match timezone {
+        Some(timezone) => Utc::now().with_timezone(timezone).date_naive(),
+        None => Local::now().date_naive(),
+    }
+}
+
+/// Zone of the bundled city nearest to a point, when one is close enough to
+/// share it
+pub fn timezone_near(latitude: f64, longitude: f64) -> Option<Tz> {
+    let city = City::nearest(latitude, longitude, |_| true)?;
+    let distance = great_circle_distance((latitude, longitude), (city.latitude, city.longitude));
+
+    (distance <= NEAREST_CITY_RADIUS).then_some(city.timezone)
+}
+
+/// Zone for a location reported with its UTC offset rather than a zone
+/// name: that of the nearest bundled city having this offset now, one of
+/// the same country when there is any. Failing that, a fixed `Etc/GMT` zone
+/// for whole hours, or the first zone having this offset.
+pub fn timezone_for_location(
+    latitude: f64,
+    longitude: f64,
+    country_code: &str,
+    offset_seconds: i32,
+) -> Tz {
+    let now = Utc::now();
+    let has_offset = |timezone: &Tz| {
+        now.with_timezone(timezone).offset().fix().local_minus_utc() == offset_seconds
+    };
+
+    let nearest = City::nearest(latitude, longitude, |city| {
+        city.country_code.eq_ignore_ascii_case(country_code) && has_offset(&city.timezone)
+    })
+    .or_else(|| City::nearest(latitude, longitude, |city| has_offset(&city.timezone)));
+    if let Some(city) = nearest {
+        return city.timezone;
+    }
+
+    // Etc/GMT zones have their sign inverted, Etc/GMT-1 is UTC+1
+    if offset_seconds % 3600 == 0 {
+        let hours = offset_seconds / 3600;
+        if let Ok(timezone) = format!("Etc/GMT{:+}", -hours).parse() {
+            return timezone;
+        }
+    }
+
+    chrono_tz::TZ_VARIANTS
+        .into_iter()
+        .find(has_offset)
+        .unwrap_or(Tz::UTC)
+}
+
+/// Wall clock time in `timezone`, moved forward when it falls in the gap of
+/// a daylight saving change
+pub fn localize(timezone: &Tz, datetime: NaiveDateTime) -> DateTime<Tz> {
+    match timezone.from_local_datetime(&datetime) {
+        LocalResult::Single(zoned) => zoned,
+        LocalResult::Ambiguous(earliest, _) => earliest,
+        LocalResult::None => localize(timezone, datetime + TimeDelta::hours(1)),
+    }
+}
+
+/// Serde helper keeping the zone name next to the instant, e.g.
+/// `2026-10-17T05:31:00+01:00 Africa/Algiers`
+pub mod zoned {
+    use super::*;
+
+    pub fn serialize<S: Serializer>(
+        datetime: &DateTime<Tz>,
+        serializer: S,
+    ) -> Result<S::Ok, S::Error> {
+        serializer.serialize_str(&format!(
+            "{} {}",
+            datetime.to_rfc3339(),
+            datetime.timezone().name()
+        ))
+    }
+
+    pub fn deserialize<'de, D: Deserializer<'de>>(
+        deserializer: D,
+    ) -> Result<DateTime<Tz>, D::Error> {
+        let value = String::deserialize(deserializer)?;
+        let (instant, zone) = value
+            .split_once(' ')
+            .ok_or_else(|| D::Error::custom("missing time zone name"))?;
+
+        let instant = DateTime::parse_from_rfc3339(instant).map_err(D::Error::custom)?;
+        let zone: Tz = zone.parse().map_err(D::Error::custom)?;
+
+        Ok(instant.with_timezone(&zone))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn keeps_half_hour_offsets() {
+        let delhi = timezone_for_location(28.6139, 77.2090, "IN", 19800);
+        assert_eq!(delhi, chrono_tz::Asia::Kolkata);
+
+        // Far from any bundled city of the country
+        let timezone = timezone_for_location(-20.0, 80.0, "", 19800);
+        let offset = Utc::now().with_timezone(&timezone).offset().fix();
+        assert_eq!(offset.local_minus_utc(), 19800);
+    }
+
+    #[test]
+    fn takes_the_zone_of_the_nearest_city_of_the_country() {
+        let istanbul = timezone_for_location(41.0082, 28.9784, "TR", 3 * 3600);
+        assert_eq!(istanbul, chrono_tz::Europe::Istanbul);
+    }
+
+    #[test]
+    fn falls_back_to_a_fixed_zone_for_whole_hours() {
+        let baker_island = timezone_for_location(0.1936, -176.4769, "UM", -12 * 3600);
+        assert_eq!(baker_island, chrono_tz::Etc::GMTPlus12);
+    }
+}
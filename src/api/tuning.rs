@@ -0,0 +1,121 @@
+use std::{collections::HashMap, str::FromStr};
+
+use chrono::TimeDelta;
+
+use crate::api::{AdhanError, ParsedPrayerTimeItem, ParsedPrayerTimesResponse, Prayer};
+
+/// Largest offset accepted, in minutes either way
+pub const MAX_OFFSET_MINUTES: i64 = 180;
+
+/// Fixed minute offsets added to each prayer, e.g. to match the times
+/// published by a local mosque
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct Tuning {
+    offsets: HashMap<Prayer, i64>,
+}
+
+impl Tuning {
+    pub fn offset(&self, prayer: &Prayer) -> i64 {
+        self.offsets.get(prayer).copied().unwrap_or(0)
+    }
+
+    pub fn set_offset(&mut self, prayer: Prayer, minutes: i64) {
+        if minutes == 0 {
+            self.offsets.remove(&prayer);
+        } else {
+            self.offsets.insert(prayer, minutes);
+        }
+    }
+
+    pub fn apply(&self, item: &mut ParsedPrayerTimeItem) {
+        for (prayer, minutes) in &self.offsets {
+            let time = item.get_prayer(prayer) + TimeDelta::minutes(*minutes);
+            item.set_prayer(prayer, time);
+        }
+    }
+}
+
+impl std::fmt::Display for Tuning {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let data: Vec<String> = Prayer::all_prayers()
+            .iter()
+            .filter(|prayer| self.offset(prayer) != 0)
+            .map(|prayer| format!("{}:{:+}", prayer.name().to_lowercase(), self.offset(prayer)))
+            .collect();
+
+        write!(f, "{}", data.join(","))
+    }
+}
+
+impl FromStr for Tuning {
+    type Err = AdhanError;
+
+    /// Parses comma separated `<prayer>:<minutes>` pairs such as
+    /// `fajr:+2, dhuhr:+3, maghrib:-1`
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let mut tuning = Tuning::default();
+
+        for pair in s.split(',').map(str::trim).filter(|pair| !pair.is_empty()) {
+            let (prayer, minutes) = pair.split_once(':').ok_or(AdhanError::InvalidTuning)?;
+
+            let prayer = Prayer::from_str(prayer.trim())?;
+            // The integer parser takes a single leading sign, rejecting "++2"
+            let minutes: i64 = minutes
+                .trim()
+                .parse()
+                .map_err(|_| AdhanError::InvalidTuning)?;
+            if minutes.abs() > MAX_OFFSET_MINUTES {
+                return Err(AdhanError::InvalidTuning);
+            }
+
+            tuning.set_offset(prayer, minutes);
+        }
+
+        Ok(tuning)
+    }
+}
+
+impl ParsedPrayerTimesResponse {
+    pub fn apply_tuning(&mut self, tuning: &Tuning) {
+        self.items.iter_mut().for_each(|item| tuning.apply(item));
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parses_offsets() {
+        let tuning = Tuning::from_str("fajr:+2, dhuhr:3,maghrib:-1,").unwrap();
+
+        assert_eq!(tuning.offset(&Prayer::Fajr), 2);
+        assert_eq!(tuning.offset(&Prayer::Dhuhr), 3);
+        assert_eq!(tuning.offset(&Prayer::Maghrib), -1);
+        assert_eq!(tuning.offset(&Prayer::Isha), 0);
+        assert_eq!(tuning.to_string(), "fajr:+2,dhuhr:+3,maghrib:-1");
+        assert_eq!(
+            Tuning::from_str("isha:-180").unwrap().offset(&Prayer::Isha),
+            -180
+        );
+    }
+
+    #[test]
+    fn rejects_malformed_offsets() {
+        for tuning in [
+            "fajr", "fajr:", "fajr:++2", "fajr:+-2", "fajr:two", "noon:+2",
+        ] {
+            assert!(Tuning::from_str(tuning).is_err(), "{tuning} was accepted");
+        }
+    }
+
+    #[test]
+    fn rejects_out_of_range_offsets() {
+        for tuning in ["fajr:181", "isha:-181", "fajr:99999999999999"] {
+            assert!(
+                matches!(Tuning::from_str(tuning), Err(AdhanError::InvalidTuning)),
+                "{tuning} was accepted"
+            );
+        }
+    }
+}
@@ -0,0 +1,5 @@
+mod player;
+mod sounds;
+
+pub use player::*;
+pub use sounds::*;
@@ -0,0 +1,254 @@
+use std::{
+    fs::File,
+    io::{BufReader, BufWriter, Write},
+    path::{Path, PathBuf},
+    sync::mpsc::{self, Receiver, Sender},
+    thread,
+    time::Duration,
+};
+
+use async_trait::async_trait;
+use rodio::{Decoder, OutputStream, Sink, Source};
+
+use crate::{
+    api::{AdhanError, Prayer},
+    audio::{AdhanSounds, AudioOutput, PlaybackSettings},
+    daemon::{EventKind, Notifier, ScheduledEvent},
+};
+
+enum Command {
+    Play(PathBuf),
+    Stop,
+}
+
+fn decode(path: &Path) -> Result<Decoder<BufReader<File>>, AdhanError> {
+    let file = File::open(path)
+        .map_err(|error| AdhanError::Audio(format!("{}: {error}", path.display())))?;
+
+    Decoder::new(BufReader::new(file))
+        .map_err(|error| AdhanError::Audio(format!("{}: {error}", path.display())))
+}
+
+/// Writes 16 bit PCM samples as a WAV file
+fn write_wav(path: &Path, channels: u16, sample_rate: u32, samples: &[i16]) -> std::io::Result<()> {
+    let mut file = BufWriter::new(File::create(path)?);
+    let data_size = (samples.len() * 2) as u32;
+    let block_align = channels * 2;
+
+    file.write_all(b"RIFF")?;
+    file.write_all(&(36 + data_size).to_le_bytes())?;
+    file.write_all(b"WAVEfmt ")?;
+    file.write_all(&16u32.to_le_bytes())?;
+    file.write_all(&1u16.to_le_bytes())?;
+    file.write_all(&channels.to_le_bytes())?;
+    file.write_all(&sample_rate.to_le_bytes())?;
+    file.write_all(&(sample_rate * block_align as u32).to_le_bytes())?;
+    file.write_all(&block_align.to_le_bytes())?;
+    file.write_all(&16u16.to_le_bytes())?;
+    file.write_all(b"data")?;
+    file.write_all(&data_size.to_le_bytes())?;
+    for sample in samples {
+        file.write_all(&sample.to_le_bytes())?;
+    }
+
+    file.flush()
+}
+
+/// Plays the commands received on `commands` until the player is dropped.
+/// The sound device is opened on this thread, its stream not being `Send`
+fn audio_thread(
+    output: AudioOutput,
+    settings: PlaybackSettings,
+    commands: Receiver<Command>,
+    ready: Sender<Result<(), AdhanError>>,
+) {
+    let device = match output {
+        AudioOutput::Device => {
+            let opened = OutputStream::try_default()
+                .map_err(|error| AdhanError::Audio(error.to_string()))
+                .and_then(|(stream, handle)| {
+                    let sink = Sink::try_new(&handle)
+                        .map_err(|error| AdhanError::Audio(error.to_string()))?;
+                    Ok((stream, sink))
+                });
+
+            match opened {
+                Ok(device) => Some(device),
+                Err(error) => {
+                    let _ = ready.send(Err(error));
+                    return;
+                }
+            }
+        }
+        AudioOutput::Null | AudioOutput::File(_) => None,
+    };
+    let _ = ready.send(Ok(()));
+
+    for command in commands {
+        let path = match command {
+            Command::Play(path) => path,
+            Command::Stop => {
+                if let Some((_, sink)) = &device {
+                    sink.stop();
+                }
+                continue;
+            }
+        };
+
+        let source = match decode(&path) {
+            Ok(source) => source
+                .convert_samples::<f32>()
+                .amplify(settings.volume)
+                // rodio panics on a fade of zero length
+                .fade_in(settings.fade_in.max(Duration::from_nanos(1))),
+            Err(error) => {
+                eprintln!("Could not play the adhan: {error}");
+                continue;
+            }
+        };
+
+        match (&output, &device) {
+            (AudioOutput::Device, Some((_, sink))) => {
+                // A new adhan replaces the one playing
+                sink.stop();
+                sink.append(source);
+                sink.play();
+            }
+            (AudioOutput::File(file), _) => {
+                let channels = source.channels();
+                let sample_rate = source.sample_rate();
+                let samples: Vec<i16> = source
+                    .map(|sample| (sample.clamp(-1.0, 1.0) * i16::MAX as f32) as i16)
+                    .collect();
+
+                if let Err(error) = write_wav(file, channels, sample_rate, &samples) {
+                    eprintln!("Could not write {}: {error}", file.display());
+                }
+            }
+            _ => source.for_each(drop),
+        }
+    }
+}
+
+/// Plays the adhan of each prayer on a thread of its own, so that it can be
+/// shared with the TUI and the daemon
+pub struct AdhanPlayer {
+    sounds: AdhanSounds,
+    commands: Sender<Command>,
+}
+
+impl AdhanPlayer {
+    /// Opens `output` and checks that every configured file can be decoded
+    pub fn spawn(
+        sounds: AdhanSounds,
+        settings: PlaybackSettings,
+        output: AudioOutput,
+    ) -> Result<Self, AdhanError> {
+        for path in sounds.default.iter().chain(sounds.prayers.values()) {
+            decode(path)?;
+        }
+
+        let (commands, receiver) = mpsc::channel();
+        let (ready, ready_receiver) = mpsc::channel();
+        thread::spawn(move || audio_thread(output, settings, receiver, ready));
+
+        ready_receiver
+            .recv()
+            .map_err(|_| AdhanError::Audio("the audio thread stopped".to_string()))??;
+
+        Ok(AdhanPlayer { sounds, commands })
+    }
+
+    /// Starts the adhan of `prayer`, if it has one
+    pub fn play(&self, prayer: &Prayer) {
+        if let Some(path) = self.sounds.sound(prayer) {
+            let _ = self.commands.send(Command::Play(path.clone()));
+        }
+    }
+
+    pub fn stop(&self) {
+        let _ = self.commands.send(Command::Stop);
+    }
+}
+
+#[async_trait]
+impl Notifier for AdhanPlayer {
+    async fn notify(&self, event: &ScheduledEvent, _place: &str) -> Result<(), AdhanError> {
+        if event.kind == EventKind::AtTime {
+            self.play(&event.prayer);
+        }
+
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::{collections::HashMap, fs};
+
+    use super::*;
+
+    const SAMPLE_RATE: u32 = 8000;
+
+    /// A tenth of a second of a stereo square wave
+    fn samples() -> Vec<i16> {
+        (0..SAMPLE_RATE / 10 * 2)
+            .map(|index| if index / 40 % 2 == 0 { 8000 } else { -8000 })
+            .collect()
+    }
+
+    fn u16_at(bytes: &[u8], offset: usize) -> u16 {
+        u16::from_le_bytes([bytes[offset], bytes[offset + 1]])
+    }
+
+    fn u32_at(bytes: &[u8], offset: usize) -> u32 {
+        u32::from_le_bytes(bytes[offset..offset + 4].try_into().unwrap())
+    }
+
+    #[test]
+    fn file_output_writes_a_pcm_wav() {
+        let dir = std::env::temp_dir().join(format!("adhan-cli-player-{}", std::process::id()));
+        fs::create_dir_all(&dir).unwrap();
+        let input = dir.join("adhan.wav");
+        let output = dir.join("played.wav");
+        write_wav(&input, 2, SAMPLE_RATE, &samples()).unwrap();
+
+        let sounds = AdhanSounds {
+            default: Some(input),
+            prayers: HashMap::new(),
+        };
+        let settings = PlaybackSettings {
+            volume: 1.0,
+            fade_in: Duration::ZERO,
+        };
+        let player =
+            AdhanPlayer::spawn(sounds, settings, AudioOutput::File(output.clone())).unwrap();
+        player.play(&Prayer::Fajr);
+
+        let data_size = samples().len() as u32 * 2;
+        let mut bytes = Vec::new();
+        for _ in 0..100 {
+            bytes = fs::read(&output).unwrap_or_default();
+            if bytes.len() == 44 + data_size as usize {
+                break;
+            }
+            thread::sleep(Duration::from_millis(50));
+        }
+        fs::remove_dir_all(&dir).unwrap();
+
+        assert_eq!(bytes.len(), 44 + data_size as usize);
+        assert_eq!(&bytes[0..4], b"RIFF");
+        assert_eq!(u32_at(&bytes, 4), 36 + data_size);
+        assert_eq!(&bytes[8..16], b"WAVEfmt ");
+        assert_eq!(u32_at(&bytes, 16), 16);
+        // PCM, stereo, 16 bits
+        assert_eq!(u16_at(&bytes, 20), 1);
+        assert_eq!(u16_at(&bytes, 22), 2);
+        assert_eq!(u32_at(&bytes, 24), SAMPLE_RATE);
+        assert_eq!(u32_at(&bytes, 28), SAMPLE_RATE * 4);
+        assert_eq!(u16_at(&bytes, 32), 4);
+        assert_eq!(u16_at(&bytes, 34), 16);
+        assert_eq!(&bytes[36..40], b"data");
+        assert_eq!(u32_at(&bytes, 40), data_size);
+    }
+}
@@ -0,0 +1,141 @@
+use std::{
+    collections::HashMap,
+    path::{Path, PathBuf},
+    str::FromStr,
+    time::Duration,
+};
+
+use crate::api::{AdhanError, Prayer};
+
+/// `path` with a leading `~` replaced by the home directory, which the
+/// shell leaves as is in `--sound-for fajr=~/adhan.mp3`
+pub fn expand_home(path: &Path) -> PathBuf {
+    if let Ok(rest) = path.strip_prefix("~")
+        && let Some(home) = dirs::home_dir()
+    {
+        return home.join(rest);
+    }
+
+    path.to_path_buf()
+}
+
+/// Audio file played for each prayer
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct AdhanSounds {
+    /// Played for the five prayers without a file of their own
+    pub default: Option<PathBuf>,
+    pub prayers: HashMap<Prayer, PathBuf>,
+}
+
+impl AdhanSounds {
+    /// File to play for `prayer`. Shurooq is not a prayer and is only
+    /// announced when given a file of its own
+    pub fn sound(&self, prayer: &Prayer) -> Option<&PathBuf> {
+        match self.prayers.get(prayer) {
+            Some(path) => Some(path),
+            None if *prayer == Prayer::Shurooq => None,
+            None => self.default.as_ref(),
+        }
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.default.is_none() && self.prayers.is_empty()
+    }
+
+    /// Sets the file of a prayer from `<prayer>=<file>`, e.g.
+    /// `fajr=~/adhan/fajr.mp3`
+    pub fn set_from_str(&mut self, s: &str) -> Result<(), AdhanError> {
+        let (prayer, path) = s
+            .split_once('=')
+            .ok_or_else(|| AdhanError::Audio(format!("expected <prayer>=<file>, got {s}")))?;
+        let prayer = Prayer::from_str(prayer.trim())?;
+
+        self.prayers
+            .insert(prayer, expand_home(Path::new(path.trim())));
+        Ok(())
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct PlaybackSettings {
+    /// Between 0 (silent) and 1 (as recorded)
+    pub volume: f32,
+    /// How long the sound takes to reach its volume
+    pub fade_in: Duration,
+}
+
+impl Default for PlaybackSettings {
+    fn default() -> Self {
+        PlaybackSettings {
+            volume: 1.0,
+            fade_in: Duration::from_secs(2),
+        }
+    }
+}
+
+/// Where the sound goes
+#[derive(Debug, Clone, PartialEq)]
+pub enum AudioOutput {
+    /// The default sound device
+    Device,
+    /// Decoded and dropped, to check the files without a sound device
+    Null,
+    /// Written as a WAV file, replaced at each prayer
+    File(PathBuf),
+}
+
+impl FromStr for AudioOutput {
+    type Err = AdhanError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.split_once(':') {
+            Some(("file", path)) if !path.is_empty() => {
+                Ok(Self::File(expand_home(Path::new(path))))
+            }
+            _ => match s.to_lowercase().as_str() {
+                "device" => Ok(Self::Device),
+                "null" => Ok(Self::Null),
+                _ => Err(AdhanError::Audio(format!(
+                    "unknown output {s}, expected device, null or file:<path>"
+                ))),
+            },
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn expands_the_home_directory() {
+        let home = dirs::home_dir().unwrap();
+
+        assert_eq!(
+            expand_home(Path::new("~/adhan/fajr.mp3")),
+            home.join("adhan/fajr.mp3")
+        );
+        assert_eq!(expand_home(Path::new("~")), home);
+        assert_eq!(
+            expand_home(Path::new("~user/fajr.mp3")),
+            PathBuf::from("~user/fajr.mp3")
+        );
+        assert_eq!(
+            expand_home(Path::new("/tmp/~/fajr.mp3")),
+            PathBuf::from("/tmp/~/fajr.mp3")
+        );
+    }
+
+    #[test]
+    fn sets_the_sound_of_a_prayer() {
+        let mut sounds = AdhanSounds::default();
+        sounds.set_from_str("fajr = ~/adhan/fajr.mp3").unwrap();
+
+        assert_eq!(
+            sounds.sound(&Prayer::Fajr),
+            Some(&dirs::home_dir().unwrap().join("adhan/fajr.mp3"))
+        );
+        assert_eq!(sounds.sound(&Prayer::Dhuhr), None);
+        assert!(sounds.set_from_str("fajr.mp3").is_err());
+    }
+}
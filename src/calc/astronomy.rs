@@ -0,0 +1,81 @@
+use chrono::{Datelike, NaiveDate};
+
+pub fn dsin(degrees: f64) -> f64 {
+    degrees.to_radians().sin()
+}
+
+pub fn dcos(degrees: f64) -> f64 {
+    degrees.to_radians().cos()
+}
+
+pub fn dtan(degrees: f64) -> f64 {
+    degrees.to_radians().tan()
+}
+
+pub fn darcsin(x: f64) -> f64 {
+    x.asin().to_degrees()
+}
+
+pub fn darccos(x: f64) -> f64 {
+    x.acos().to_degrees()
+}
+
+pub fn darctan2(y: f64, x: f64) -> f64 {
+    y.atan2(x).to_degrees()
+}
+
+pub fn darccot(x: f64) -> f64 {
+    (1.0 / x).atan().to_degrees()
+}
+
+pub fn fix_angle(angle: f64) -> f64 {
+    angle.rem_euclid(360.0)
+}
+
+pub fn fix_hour(hour: f64) -> f64 {
+    hour.rem_euclid(24.0)
+}
+
+/// Julian date at midnight UTC for a gregorian calendar date
+pub fn julian_date(date: NaiveDate) -> f64 {
+    let mut year = date.year() as f64;
+    let mut month = date.month() as f64;
+    let day = date.day() as f64;
+
+    if month <= 2.0 {
+        year -= 1.0;
+        month += 12.0;
+    }
+
+    let a = (year / 100.0).floor();
+    let b = 2.0 - a + (a / 4.0).floor();
+
+    (365.25 * (year + 4716.0)).floor() + (30.6001 * (month + 1.0)).floor() + day + b - 1524.5
+}
+
+pub struct SunPosition {
+    /// Solar declination in degrees
+    pub declination: f64,
+    /// Equation of time in hours
+    pub equation_of_time: f64,
+}
+
+impl SunPosition {
+    /// Low precision solar coordinates, accurate to about a minute of time
+    /// between 1950 and 2050
+    pub fn at(julian_date: f64) -> Self {
+        let d = julian_date - 2451545.0;
+        let g = fix_angle(357.529 + 0.98560028 * d);
+        let q = fix_angle(280.459 + 0.98564736 * d);
+        let l = fix_angle(q + 1.915 * dsin(g) + 0.020 * dsin(2.0 * g));
+
+        let e = 23.439 - 0.00000036 * d;
+
+        let right_ascension = fix_hour(darctan2(dcos(e) * dsin(l), dcos(l)) / 15.0);
+
+        SunPosition {
+            declination: darcsin(dsin(e) * dsin(l)),
+            equation_of_time: q / 15.0 - right_ascension,
+        }
+    }
+}
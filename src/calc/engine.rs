@@ -0,0 +1,514 @@
+use chrono::{NaiveDate, NaiveTime, Offset};
+use chrono_tz::Tz;
+
+use crate::api::{
+    AdhanError, DataSource, ParsedLocation, ParsedPrayerTimeItem, ParsedPrayerTimesResponse,
+    Prayer, PrayerTimesPeriod, localize,
+};
+
+use super::astronomy::{SunPosition, darccos, darccot, dcos, dsin, dtan, fix_hour, julian_date};
+use super::{
+    CalculationSettings, HighLatitudeRule, IshaRule, MethodParams, NEAREST_LATITUDE,
+    qibla_direction,
+};
+
+#[derive(Debug, Clone)]
+pub struct Coordinates {
+    pub latitude: f64,
+    pub longitude: f64,
+    /// Elevation above sea level in meters
+    pub elevation: f64,
+}
+
+/// Prayer times of a single day, in fractional hours
+#[derive(Debug, Clone)]
+struct DayTimes {
+    fajr: f64,
+    sunrise: f64,
+    dhuhr: f64,
+    asr: f64,
+    sunset: f64,
+    maghrib: f64,
+    isha: f64,
+}
+
+impl DayTimes {
+    fn initial() -> Self {
+        DayTimes {
+            fajr: 5.0,
+            sunrise: 6.0,
+            dhuhr: 12.0,
+            asr: 13.0,
+            sunset: 18.0,
+            maghrib: 18.0,
+            isha: 18.0,
+        }
+    }
+}
+
+struct Calculator<'a> {
+    coordinates: &'a Coordinates,
+    params: MethodParams,
+    asr_shadow_factor: f64,
+    high_latitude_rule: HighLatitudeRule,
+    julian_date: f64,
+}
+
+impl Calculator<'_> {
+    fn mid_day(&self, day_portion: f64) -> f64 {
+        let eqt = SunPosition::at(self.julian_date + day_portion).equation_of_time;
+        fix_hour(12.0 - eqt)
+    }
+
+    /// Time at which the sun reaches `angle` degrees below the horizon,
+    /// before noon when `before_noon` is set. NaN if it never does.
+    fn sun_angle_time(&self, angle: f64, day_portion: f64, before_noon: bool) -> f64 {
+        let latitude = self.coordinates.latitude;
+        let declination = SunPosition::at(self.julian_date + day_portion).declination;
+        let noon = self.mid_day(day_portion);
+
+        let cos_hour_angle = (-dsin(angle) - dsin(declination) * dsin(latitude))
+            / (dcos(declination) * dcos(latitude));
+        let hour_angle = darccos(cos_hour_angle) / 15.0;
+
+        if before_noon {
+            noon - hour_angle
+        } else {
+            noon + hour_angle
+        }
+    }
+
+    fn asr_time(&self, shadow_factor: f64, day_portion: f64) -> f64 {
+        let declination = SunPosition::at(self.julian_date + day_portion).declination;
+        let angle = -darccot(shadow_factor + dtan((self.coordinates.latitude - declination).abs()));
+
+        self.sun_angle_time(angle, day_portion, false)
+    }
+
+    fn rise_set_angle(&self) -> f64 {
+        0.833 + 0.0347 * self.coordinates.elevation.max(0.0).sqrt()
+    }
+
+    fn compute(&self, times: &DayTimes) -> DayTimes {
+        let rise_set_angle = self.rise_set_angle();
+        let params = &self.params;
+
+        let sunset = self.sun_angle_time(rise_set_angle, times.sunset / 24.0, false);
+        let maghrib = match params.maghrib_angle {
+            Some(angle) => self.sun_angle_time(angle, times.maghrib / 24.0, false),
+            None => sunset,
+        };
+        let isha = match params.isha {
+            IshaRule::Angle(angle) => self.sun_angle_time(angle, times.isha / 24.0, false),
+            IshaRule::Interval(minutes) => maghrib + minutes as f64 / 60.0,
+        };
+
+        DayTimes {
+            fajr: self.sun_angle_time(params.fajr_angle, times.fajr / 24.0, true),
+            sunrise: self.sun_angle_time(rise_set_angle, times.sunrise / 24.0, true),
+            dhuhr: self.mid_day(times.dhuhr / 24.0),
+            asr: self.asr_time(self.asr_shadow_factor, times.asr / 24.0),
+            sunset,
+            maghrib,
+            isha,
+        }
+    }
+
+    /// Replaces twilight times that are never reached, or that are too far
+    /// into the night, following the high latitude rule
+    fn adjust_high_latitudes(&self, times: &mut DayTimes) {
+        let night = fix_hour(times.sunrise - times.sunset);
+        if !night.is_finite() {
+            return;
+        }
+
+        if self.high_latitude_rule == HighLatitudeRule::NearestLatitude {
+            self.adjust_to_nearest_latitude(times, night);
+            return;
+        }
+
+        let params = &self.params;
+        let portion =
+            |angle: f64| self.high_latitude_rule.night_portion(angle).unwrap_or(1.0) * night;
+
+        let fajr_portion = portion(params.fajr_angle);
+        if !times.fajr.is_finite() || fix_hour(times.sunrise - times.fajr) > fajr_portion {
+            times.fajr = times.sunrise - fajr_portion;
+        }
+
+        if let Some(angle) = params.maghrib_angle {
+            let maghrib_portion = portion(angle);
+            if !times.maghrib.is_finite()
+                || fix_hour(times.maghrib - times.sunset) > maghrib_portion
+            {
+                times.maghrib = times.sunset + maghrib_portion;
+            }
+        }
+
+        match params.isha {
+            IshaRule::Angle(angle) => {
+                let isha_portion = portion(angle);
+                if !times.isha.is_finite() || fix_hour(times.isha - times.sunset) > isha_portion {
+                    times.isha = times.sunset + isha_portion;
+                }
+            }
+            IshaRule::Interval(minutes) => times.isha = times.maghrib + minutes as f64 / 60.0,
+        }
+    }
+
+    /// Keeps the twilight durations, as fractions of the night, of the
+    /// nearest latitude where the sun still reaches the twilight angles
+    fn adjust_to_nearest_latitude(&self, times: &mut DayTimes, night: f64) {
+        if times.fajr.is_finite() && times.maghrib.is_finite() && times.isha.is_finite() {
+            return;
+        }
+
+        let coordinates = Coordinates {
+            latitude: NEAREST_LATITUDE.copysign(self.coordinates.latitude),
+            ..self.coordinates.clone()
+        };
+        let reference = Calculator {
+            coordinates: &coordinates,
+            high_latitude_rule: HighLatitudeRule::AngleBased,
+            ..*self
+        }
+        .compute(&DayTimes::initial());
+        let reference_night = fix_hour(reference.sunrise - reference.sunset);
+        let portion = |duration: f64| fix_hour(duration) / reference_night * night;
+
+        if !times.fajr.is_finite() {
+            times.fajr = times.sunrise - portion(reference.sunrise - reference.fajr);
+        }
+        if !times.maghrib.is_finite() {
+            times.maghrib = times.sunset + portion(reference.maghrib - reference.sunset);
+        }
+        if let IshaRule::Interval(minutes) = self.params.isha {
+            times.isha = times.maghrib + minutes as f64 / 60.0;
+        } else if !times.isha.is_finite() {
+            times.isha = times.sunset + portion(reference.isha - reference.sunset);
+        }
+    }
+}
+
+fn hours_to_time(hours: f64, prayer: Prayer) -> Result<NaiveTime, AdhanError> {
+    if !hours.is_finite() {
+        return Err(AdhanError::UnreachableSunAngle(prayer));
+    }
+
+    let minutes = (fix_hour(hours) * 60.0).round() as u32 % (24 * 60);
+
+    Ok(NaiveTime::from_num_seconds_from_midnight_opt(minutes * 60, 0).unwrap())
+}
+
+/// Degrees by which the latitude moves toward the equator while looking for
+/// one where the sun rises and sets
+const POLAR_LATITUDE_STEP: f64 = 0.5;
+
+/// Computes the prayer times of `date` at `coordinates`, in the offset
+/// `timezone` has at noon that day. During polar day or night, the times
+/// are those of the nearest latitude where the sun still rises and sets.
+pub fn calculate_prayer_times(
+    coordinates: &Coordinates,
+    settings: &CalculationSettings,
+    date: NaiveDate,
+    timezone: &Tz,
+) -> Result<ParsedPrayerTimeItem, AdhanError> {
+    let mut reference = coordinates.clone();
+    let times = loop {
+        let calculator = Calculator {
+            coordinates: &reference,
+            params: settings.method.params(),
+            asr_shadow_factor: settings.asr_school.shadow_factor(),
+            high_latitude_rule: settings.high_latitude_rule,
+            julian_date: julian_date(date) - coordinates.longitude / (15.0 * 24.0),
+        };
+
+        let mut times = calculator.compute(&DayTimes::initial());
+        let sun_rises_and_sets = times.sunrise.is_finite() && times.sunset.is_finite();
+        if sun_rises_and_sets || reference.latitude.abs() <= NEAREST_LATITUDE {
+            calculator.adjust_high_latitudes(&mut times);
+            break times;
+        }
+
+        reference.latitude -= POLAR_LATITUDE_STEP.copysign(reference.latitude);
+    };
+
+    let noon = localize(timezone, date.and_hms_opt(12, 0, 0).unwrap());
+    let offset = noon.offset().fix().local_minus_utc() as f64 / 3600.0;
+    let shift = offset - coordinates.longitude / 15.0;
+
+    let times = [
+        hours_to_time(times.fajr + shift, Prayer::Fajr)?,
+        hours_to_time(times.sunrise + shift, Prayer::Shurooq)?,
+        hours_to_time(times.dhuhr + shift, Prayer::Dhuhr)?,
+        hours_to_time(times.asr + shift, Prayer::Asr)?,
+        hours_to_time(times.maghrib + shift, Prayer::Maghrib)?,
+        hours_to_time(times.isha + shift, Prayer::Isha)?,
+    ];
+
+    Ok(ParsedPrayerTimeItem::from_local_times(
+        timezone, date, times, None,
+    ))
+}
+
+/// Builds a full response without any network access, `timezone` being the
+/// local time of `coordinates`
+pub fn get_prayer_data_by_coordinates(
+    city: &str,
+    coordinates: &Coordinates,
+    settings: &CalculationSettings,
+    period: PrayerTimesPeriod,
+    today: NaiveDate,
+    timezone: Tz,
+) -> Result<ParsedPrayerTimesResponse, AdhanError> {
+    let items = period
+        .dates_from(today)
+        .into_iter()
+        .map(|date| calculate_prayer_times(coordinates, settings, date, &timezone))
+        .collect::<Result<Vec<_>, _>>()?;
+
+    let location = ParsedLocation {
+        state: String::new(),
+        city: city.to_string(),
+        country: String::new(),
+        timezone,
+        country_code: String::new(),
+        qibla_direction: qibla_direction(coordinates.latitude, coordinates.longitude),
+        latitude: coordinates.latitude,
+        longitude: coordinates.longitude,
+    };
+
+    Ok(ParsedPrayerTimesResponse {
+        location,
+        items,
+        period,
+        method_name: settings.method.name().to_string(),
+        asr_school: settings.asr_school,
+        high_latitude_rule: Some(settings.high_latitude_rule),
+        source: DataSource::Live,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use chrono::{DateTime, NaiveTime, TimeDelta};
+    use chrono_tz::Tz;
+
+    use super::*;
+    use crate::calc::{AsrSchool, CalculationMethod};
+
+    fn settings(method: CalculationMethod, rule: HighLatitudeRule) -> CalculationSettings {
+        CalculationSettings {
+            method,
+            asr_school: AsrSchool::Shafii,
+            high_latitude_rule: rule,
+        }
+    }
+
+    fn at(latitude: f64, longitude: f64) -> Coordinates {
+        Coordinates {
+            latitude,
+            longitude,
+            elevation: 0.0,
+        }
+    }
+
+    fn date(year: i32, month: u32, day: u32) -> NaiveDate {
+        NaiveDate::from_ymd_opt(year, month, day).unwrap()
+    }
+
+    /// Asserts that `time` is within a minute of `expected`, published
+    /// tables rounding differently
+    fn assert_near(time: DateTime<Tz>, expected: &str) {
+        let expected = NaiveTime::parse_from_str(expected, "%H:%M").unwrap();
+        let difference = (time.time() - expected).num_seconds().abs();
+        assert!(
+            difference <= 60,
+            "{} is not within a minute of {expected}",
+            time.time()
+        );
+    }
+
+    #[test]
+    fn makkah_umm_al_qura() {
+        let item = calculate_prayer_times(
+            &at(21.4225, 39.8262),
+            &settings(CalculationMethod::UmmAlQura, HighLatitudeRule::default()),
+            date(2026, 1, 1),
+            &chrono_tz::Asia::Riyadh,
+        )
+        .unwrap();
+
+        assert_near(item.fajr, "05:37");
+        assert_near(item.shurooq, "06:59");
+        assert_near(item.maghrib, "17:49");
+        assert_near(item.isha, "19:20");
+        assert_eq!(item.isha - item.maghrib, TimeDelta::minutes(90));
+    }
+
+    #[test]
+    fn new_york_isna_in_summer_time() {
+        let item = calculate_prayer_times(
+            &at(40.7128, -74.0060),
+            &settings(CalculationMethod::Isna, HighLatitudeRule::default()),
+            date(2026, 6, 21),
+            &chrono_tz::America::New_York,
+        )
+        .unwrap();
+
+        assert_near(item.fajr, "03:45");
+        assert_near(item.shurooq, "05:25");
+        assert_near(item.dhuhr, "12:57");
+        assert_near(item.maghrib, "20:31");
+    }
+
+    #[test]
+    fn high_latitude_rules_keep_the_night_in_order() {
+        let oslo = at(59.9139, 10.7522);
+        let midsummer = date(2026, 6, 21);
+
+        for rule in [
+            HighLatitudeRule::MiddleOfTheNight,
+            HighLatitudeRule::OneSeventh,
+            HighLatitudeRule::AngleBased,
+            HighLatitudeRule::NearestLatitude,
+        ] {
+            let item = calculate_prayer_times(
+                &oslo,
+                &settings(CalculationMethod::MuslimWorldLeague, rule),
+                midsummer,
+                &chrono_tz::Europe::Oslo,
+            )
+            .unwrap();
+
+            assert!(item.fajr < item.shurooq, "{rule:?}");
+            assert!(item.maghrib < item.isha, "{rule:?}");
+            assert!(item.isha - item.maghrib < TimeDelta::hours(4), "{rule:?}");
+        }
+    }
+
+    #[test]
+    fn middle_of_the_night_splits_the_night() {
+        let item = calculate_prayer_times(
+            &at(59.9139, 10.7522),
+            &settings(
+                CalculationMethod::MuslimWorldLeague,
+                HighLatitudeRule::MiddleOfTheNight,
+            ),
+            date(2026, 6, 21),
+            &chrono_tz::Europe::Oslo,
+        )
+        .unwrap();
+
+        // Both fall back to the middle of the night, sunrise of the next day
+        // being close enough to that of this one
+        let night = item.shurooq + TimeDelta::days(1) - item.maghrib;
+        assert!((item.isha - item.maghrib - night / 2).num_minutes().abs() <= 1);
+        assert!((item.shurooq - item.fajr - night / 2).num_minutes().abs() <= 1);
+    }
+
+    #[test]
+    fn one_seventh_keeps_a_seventh_of_the_night() {
+        let item = calculate_prayer_times(
+            &at(59.9139, 10.7522),
+            &settings(
+                CalculationMethod::MuslimWorldLeague,
+                HighLatitudeRule::OneSeventh,
+            ),
+            date(2026, 6, 21),
+            &chrono_tz::Europe::Oslo,
+        )
+        .unwrap();
+
+        let night = item.shurooq + TimeDelta::days(1) - item.maghrib;
+        assert!((item.isha - item.maghrib - night / 7).num_minutes().abs() <= 1);
+        assert!((item.shurooq - item.fajr - night / 7).num_minutes().abs() <= 1);
+    }
+
+    #[test]
+    fn polar_day_and_night_use_a_latitude_where_the_sun_sets() {
+        let tromso = at(69.6492, 18.9553);
+
+        for day in [date(2026, 6, 21), date(2026, 12, 21)] {
+            let item = calculate_prayer_times(
+                &tromso,
+                &settings(
+                    CalculationMethod::MuslimWorldLeague,
+                    HighLatitudeRule::default(),
+                ),
+                day,
+                &chrono_tz::Europe::Oslo,
+            )
+            .unwrap();
+
+            assert!(item.fajr < item.shurooq, "{day}");
+            assert!(item.shurooq < item.dhuhr, "{day}");
+            assert!(item.dhuhr < item.asr, "{day}");
+            assert!(item.asr < item.maghrib, "{day}");
+            assert!(item.maghrib < item.isha, "{day}");
+        }
+    }
+
+    #[test]
+    fn a_year_in_the_arctic_is_computed_with_every_rule() {
+        for rule in [
+            HighLatitudeRule::MiddleOfTheNight,
+            HighLatitudeRule::OneSeventh,
+            HighLatitudeRule::AngleBased,
+            HighLatitudeRule::NearestLatitude,
+        ] {
+            let response = get_prayer_data_by_coordinates(
+                "Longyearbyen",
+                &at(78.2232, 15.6267),
+                &settings(CalculationMethod::MuslimWorldLeague, rule),
+                PrayerTimesPeriod::Yearly,
+                NaiveDate::from_ymd_opt(2026, 10, 17).unwrap(),
+                chrono_tz::Arctic::Longyearbyen,
+            )
+            .unwrap();
+
+            assert!(response.items.len() >= 365, "{rule:?}");
+        }
+    }
+
+    #[test]
+    fn isha_after_midnight_is_on_the_next_day() {
+        let item = calculate_prayer_times(
+            &at(59.9139, 10.7522),
+            &settings(
+                CalculationMethod::MuslimWorldLeague,
+                HighLatitudeRule::AngleBased,
+            ),
+            date(2026, 6, 21),
+            &chrono_tz::Europe::Oslo,
+        )
+        .unwrap();
+
+        assert_eq!(item.date, date(2026, 6, 21));
+        assert_eq!(item.maghrib.date_naive(), date(2026, 6, 21));
+        assert_eq!(item.isha.date_naive(), date(2026, 6, 22));
+    }
+
+    #[test]
+    fn from_local_times_rolls_over_midnight() {
+        let time = |text: &str| NaiveTime::parse_from_str(text, "%H:%M").unwrap();
+        let item = ParsedPrayerTimeItem::from_local_times(
+            &chrono_tz::Europe::London,
+            date(2026, 6, 21),
+            [
+                time("02:40"),
+                time("04:43"),
+                time("13:15"),
+                time("17:32"),
+                time("22:03"),
+                time("01:15"),
+            ],
+            None,
+        );
+
+        assert_eq!(item.fajr.date_naive(), date(2026, 6, 21));
+        assert_eq!(item.maghrib.date_naive(), date(2026, 6, 21));
+        assert_eq!(item.isha.date_naive(), date(2026, 6, 22));
+        assert_eq!(item.isha.time(), time("01:15"));
+    }
+}
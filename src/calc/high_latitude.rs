@@ -0,0 +1,72 @@
+use std::str::FromStr;
+
+use serde::{Deserialize, Serialize};
+
+use crate::api::AdhanError;
+
+/// Latitude used as reference by [`HighLatitudeRule::NearestLatitude`]
+pub const NEAREST_LATITUDE: f64 = 48.5;
+
+/// How Fajr and Isha are estimated where the sun does not go deep enough
+/// below the horizon for the twilight angles of the method
+#[derive(Debug, Clone, Copy, PartialEq, Default, Serialize, Deserialize)]
+pub enum HighLatitudeRule {
+    /// Fajr and Isha never further than half of the night from sunrise and sunset
+    #[default]
+    MiddleOfTheNight,
+    /// Fajr and Isha never further than a seventh of the night from sunrise and sunset
+    OneSeventh,
+    /// Fajr and Isha never further than angle/60 of the night from sunrise and sunset
+    AngleBased,
+    /// Twilight durations of the nearest latitude where the angles are reached
+    NearestLatitude,
+}
+
+impl HighLatitudeRule {
+    pub fn name(&self) -> &'static str {
+        match self {
+            Self::MiddleOfTheNight => "Middle of the night",
+            Self::OneSeventh => "One-seventh of the night",
+            Self::AngleBased => "Angle-based",
+            Self::NearestLatitude => "Nearest latitude",
+        }
+    }
+
+    /// Largest fraction of the night allowed between the twilight and the
+    /// sunrise or sunset, `None` for rules that are not portion based
+    pub fn night_portion(&self, angle: f64) -> Option<f64> {
+        match self {
+            Self::MiddleOfTheNight => Some(1.0 / 2.0),
+            Self::OneSeventh => Some(1.0 / 7.0),
+            Self::AngleBased => Some(angle / 60.0),
+            Self::NearestLatitude => None,
+        }
+    }
+}
+
+impl std::fmt::Display for HighLatitudeRule {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let data = match self {
+            Self::MiddleOfTheNight => "middle-of-the-night",
+            Self::OneSeventh => "one-seventh",
+            Self::AngleBased => "angle-based",
+            Self::NearestLatitude => "nearest-latitude",
+        };
+
+        write!(f, "{}", data)
+    }
+}
+
+impl FromStr for HighLatitudeRule {
+    type Err = AdhanError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.to_lowercase().as_str() {
+            "middle-of-the-night" | "middle" => Ok(Self::MiddleOfTheNight),
+            "one-seventh" | "seventh" => Ok(Self::OneSeventh),
+            "angle-based" | "angle" => Ok(Self::AngleBased),
+            "nearest-latitude" | "nearest" => Ok(Self::NearestLatitude),
+            _ => Err(AdhanError::InvalidHighLatitudeRule),
+        }
+    }
+}
@@ -0,0 +1,300 @@
+use std::str::FromStr;
+
+use serde::{Deserialize, Serialize};
+
+use crate::api::AdhanError;
+
+use super::HighLatitudeRule;
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum IshaRule {
+    /// Sun angle below the horizon in degrees
+    Angle(f64),
+    /// Fixed number of minutes after Maghrib
+    Interval(u32),
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct MethodParams {
+    pub fajr_angle: f64,
+    pub isha: IshaRule,
+    /// Sun angle for Maghrib, sunset when `None`
+    pub maghrib_angle: Option<f64>,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Default)]
+pub enum CalculationMethod {
+    #[default]
+    MuslimWorldLeague,
+    Isna,
+    Egyptian,
+    UmmAlQura,
+    Karachi,
+    Tehran,
+    Gulf,
+    Kuwait,
+    Qatar,
+    Singapore,
+    Turkey,
+    Custom(MethodParams),
+}
+
+impl CalculationMethod {
+    pub fn name(&self) -> &'static str {
+        match self {
+            Self::MuslimWorldLeague => "Muslim World League",
+            Self::Isna => "Islamic Society of North America",
+            Self::Egyptian => "Egyptian General Authority of Survey",
+            Self::UmmAlQura => "Umm al-Qura University, Makkah",
+            Self::Karachi => "University of Islamic Sciences, Karachi",
+            Self::Tehran => "Institute of Geophysics, University of Tehran",
+            Self::Gulf => "Gulf Region",
+            Self::Kuwait => "Kuwait",
+            Self::Qatar => "Qatar",
+            Self::Singapore => "Majlis Ugama Islam Singapura",
+            Self::Turkey => "Diyanet İşleri Başkanlığı, Turkey",
+            Self::Custom(_) => "Custom",
+        }
+    }
+
+    pub fn params(&self) -> MethodParams {
+        let (fajr_angle, isha, maghrib_angle) = match self {
+            Self::MuslimWorldLeague => (18.0, IshaRule::Angle(17.0), None),
+            Self::Isna => (15.0, IshaRule::Angle(15.0), None),
+            Self::Egyptian => (19.5, IshaRule::Angle(17.5), None),
+            Self::UmmAlQura => (18.5, IshaRule::Interval(90), None),
+            Self::Karachi => (18.0, IshaRule::Angle(18.0), None),
+            Self::Tehran => (17.7, IshaRule::Angle(14.0), Some(4.5)),
+            Self::Gulf => (19.5, IshaRule::Interval(90), None),
+            Self::Kuwait => (18.0, IshaRule::Angle(17.5), None),
+            Self::Qatar => (18.0, IshaRule::Interval(90), None),
+            Self::Singapore => (20.0, IshaRule::Angle(18.0), None),
+            Self::Turkey => (18.0, IshaRule::Angle(17.0), None),
+            Self::Custom(params) => return *params,
+        };
+
+        MethodParams {
+            fajr_angle,
+            isha,
+            maghrib_angle,
+        }
+    }
+
+    /// Method identifier understood by muslimsalat.com, if it supports it.
+    /// The Hanafi Asr is only available there with the Karachi method.
+    pub fn muslimsalat_id(&self, asr_school: AsrSchool) -> Option<u8> {
+        match (self, asr_school) {
+            (Self::Karachi, AsrSchool::Hanafi) => Some(3),
+            (_, AsrSchool::Hanafi) => None,
+            (Self::Egyptian, _) => Some(1),
+            (Self::Karachi, _) => Some(2),
+            (Self::Isna, _) => Some(4),
+            (Self::MuslimWorldLeague, _) => Some(5),
+            (Self::UmmAlQura, _) => Some(6),
+            _ => None,
+        }
+    }
+}
+
+impl std::fmt::Display for CalculationMethod {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let data = match self {
+            Self::MuslimWorldLeague => "mwl",
+            Self::Isna => "isna",
+            Self::Egyptian => "egyptian",
+            Self::UmmAlQura => "umm-al-qura",
+            Self::Karachi => "karachi",
+            Self::Tehran => "tehran",
+            Self::Gulf => "gulf",
+            Self::Kuwait => "kuwait",
+            Self::Qatar => "qatar",
+            Self::Singapore => "singapore",
+            Self::Turkey => "turkey",
+            Self::Custom(params) => {
+                return match params.isha {
+                    IshaRule::Angle(angle) => write!(f, "custom:{},{}", params.fajr_angle, angle),
+                    IshaRule::Interval(minutes) => {
+                        write!(f, "custom:{},{}min", params.fajr_angle, minutes)
+                    }
+                };
+            }
+        };
+
+        write!(f, "{}", data)
+    }
+}
+
+impl FromStr for CalculationMethod {
+    type Err = AdhanError;
+
+    /// Accepts a method name, or `custom:<fajr angle>,<isha angle>` where the
+    /// Isha part may also be a fixed interval such as `90min`
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let s = s.to_lowercase();
+
+        if let Some(custom) = s.strip_prefix("custom:") {
+            return parse_custom(custom).ok_or(AdhanError::InvalidMethod);
+        }
+
+        match s.as_str() {
+            "mwl" => Ok(Self::MuslimWorldLeague),
+            "isna" => Ok(Self::Isna),
+            "egyptian" | "egypt" => Ok(Self::Egyptian),
+            "umm-al-qura" | "makkah" => Ok(Self::UmmAlQura),
+            "karachi" => Ok(Self::Karachi),
+            "tehran" => Ok(Self::Tehran),
+            "gulf" => Ok(Self::Gulf),
+            "kuwait" => Ok(Self::Kuwait),
+            "qatar" => Ok(Self::Qatar),
+            "singapore" => Ok(Self::Singapore),
+            "turkey" => Ok(Self::Turkey),
+            _ => Err(AdhanError::InvalidMethod),
+        }
+    }
+}
+
+/// Parses a sun angle below the horizon, which is between 0 and 90 degrees
+fn parse_angle(s: &str) -> Option<f64> {
+    let angle: f64 = s.trim().parse().ok()?;
+
+    (angle > 0.0 && angle <= 90.0).then_some(angle)
+}
+
+fn parse_custom(s: &str) -> Option<CalculationMethod> {
+    let (fajr, isha) = s.split_once(',')?;
+
+    let fajr_angle = parse_angle(fajr)?;
+    let isha = isha.trim();
+    let isha = match isha.strip_suffix("min") {
+        Some(minutes) => IshaRule::Interval(minutes.parse().ok()?),
+        None => IshaRule::Angle(parse_angle(isha)?),
+    };
+
+    Some(CalculationMethod::Custom(MethodParams {
+        fajr_angle,
+        isha,
+        maghrib_angle: None,
+    }))
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Default, Serialize, Deserialize)]
+pub enum AsrSchool {
+    /// Shafi'i, Maliki and Hanbali: shadow length equal to the object
+    #[default]
+    Shafii,
+    /// Hanafi: shadow length twice the object
+    Hanafi,
+}
+
+impl AsrSchool {
+    pub fn name(&self) -> &'static str {
+        match self {
+            Self::Shafii => "Shafi'i",
+            Self::Hanafi => "Hanafi",
+        }
+    }
+
+    pub fn shadow_factor(&self) -> f64 {
+        match self {
+            Self::Shafii => 1.0,
+            Self::Hanafi => 2.0,
+        }
+    }
+}
+
+impl std::fmt::Display for AsrSchool {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let data = match self {
+            Self::Shafii => "shafii",
+            Self::Hanafi => "hanafi",
+        };
+
+        write!(f, "{}", data)
+    }
+}
+
+impl FromStr for AsrSchool {
+    type Err = AdhanError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.to_lowercase().as_str() {
+            "shafii" | "shafi" | "standard" | "1" => Ok(Self::Shafii),
+            "hanafi" | "2" => Ok(Self::Hanafi),
+            _ => Err(AdhanError::InvalidAsrSchool),
+        }
+    }
+}
+
+/// Everything besides the location that affects the computed times
+#[derive(Debug, Clone, Copy, PartialEq, Default)]
+pub struct CalculationSettings {
+    pub method: CalculationMethod,
+    pub asr_school: AsrSchool,
+    pub high_latitude_rule: HighLatitudeRule,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parses_named_methods() {
+        assert_eq!(
+            CalculationMethod::from_str("ISNA").unwrap(),
+            CalculationMethod::Isna
+        );
+        assert_eq!(
+            CalculationMethod::from_str("makkah").unwrap(),
+            CalculationMethod::UmmAlQura
+        );
+        for method in ["mwl", "egyptian", "umm-al-qura", "tehran", "turkey"] {
+            let parsed = CalculationMethod::from_str(method).unwrap();
+            assert_eq!(parsed.to_string(), method);
+        }
+    }
+
+    #[test]
+    fn parses_custom_methods() {
+        let method = CalculationMethod::from_str("custom:18.5, 17").unwrap();
+        assert_eq!(
+            method.params(),
+            MethodParams {
+                fajr_angle: 18.5,
+                isha: IshaRule::Angle(17.0),
+                maghrib_angle: None,
+            }
+        );
+        assert_eq!(method.to_string(), "custom:18.5,17");
+
+        let method = CalculationMethod::from_str("custom:19,90min").unwrap();
+        assert_eq!(method.params().isha, IshaRule::Interval(90));
+        assert_eq!(method.to_string(), "custom:19,90min");
+    }
+
+    #[test]
+    fn rejects_invalid_methods() {
+        for method in [
+            "",
+            "unknown",
+            "custom:",
+            "custom:18",
+            "custom:nan,17",
+            "custom:18,inf",
+            "custom:-18,17",
+            "custom:18,-17",
+            "custom:0,17",
+            "custom:91,17",
+            "custom:18,120",
+            "custom:18,-90min",
+            "custom:18,ninetymin",
+        ] {
+            assert!(
+                matches!(
+                    CalculationMethod::from_str(method),
+                    Err(AdhanError::InvalidMethod)
+                ),
+                "{method} was accepted"
+            );
+        }
+    }
+}
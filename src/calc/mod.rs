@@ -0,0 +1,12 @@
+mod astronomy;
+mod engine;
+mod high_latitude;
+mod method;
+mod provider;
+mod qibla;
+
+pub use engine::*;
+pub use high_latitude::*;
+pub use method::*;
+pub use provider::*;
+pub use qibla::*;
@@ -0,0 +1,126 @@
+use async_trait::async_trait;
+use chrono_tz::Tz;
+
+use crate::api::{
+    AdhanError, ParsedLocation, ParsedPrayerTimesResponse, PrayerTimesProvider, PrayerTimesQuery,
+    system_timezone, timezone_near, today_in,
+};
+
+use super::get_prayer_data_by_coordinates;
+
+/// Computes the prayer times locally, without any network access
+pub struct OfflineProvider;
+
+#[async_trait]
+impl PrayerTimesProvider for OfflineProvider {
+    fn id(&self) -> &'static str {
+        "offline"
+    }
+
+    fn name(&self) -> &'static str {
+        "Offline calculation"
+    }
+
+    fn accepts_coordinates(&self) -> bool {
+        true
+    }
+
+    fn accepts_names(&self) -> bool {
+        false
+    }
+
+    async fn get_prayer_times(
+        &self,
+        query: &PrayerTimesQuery,
+    ) -> Result<ParsedPrayerTimesResponse, AdhanError> {
+        let coordinates = query
+            .coordinates
+            .as_ref()
+            .ok_or(AdhanError::CoordinatesRequired)?;
+        let timezone = query
+            .timezone
+            .or_else(|| timezone_near(coordinates.latitude, coordinates.longitude))
+            .or_else(system_timezone)
+            .unwrap_or(Tz::UTC);
+
+        let mut response = get_prayer_data_by_coordinates(
+            &query.city,
+            coordinates,
+            &query.settings(),
+            query.period.clone(),
+            query.date.unwrap_or_else(|| today_in(Some(&timezone))),
+            timezone,
+        )?;
+
+        if let Some(location) = &query.location {
+            response.location = ParsedLocation {
+                timezone,
+                ..location.clone()
+            };
+        }
+
+        Ok(response)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{api::PrayerTimesPeriod, calc::Coordinates};
+
+    fn query(latitude: f64, longitude: f64) -> PrayerTimesQuery {
+        PrayerTimesQuery {
+            city: format!("{latitude}, {longitude}"),
+            coordinates: Some(Coordinates {
+                latitude,
+                longitude,
+                elevation: 0.0,
+            }),
+            period: PrayerTimesPeriod::Daily,
+            date: None,
+            method: None,
+            asr_school: None,
+            high_latitude_rule: None,
+            timezone: None,
+            location: None,
+        }
+    }
+
+    #[tokio::test]
+    async fn finds_the_zone_from_the_coordinates() {
+        let response = OfflineProvider
+            .get_prayer_times(&query(59.91, 10.75))
+            .await
+            .unwrap();
+        assert_eq!(response.location.timezone, chrono_tz::Europe::Oslo);
+
+        let response = OfflineProvider
+            .get_prayer_times(&query(-6.2, 106.85))
+            .await
+            .unwrap();
+        assert_eq!(response.location.timezone, chrono_tz::Asia::Jakarta);
+        assert_eq!(response.items[0].fajr.timezone(), chrono_tz::Asia::Jakarta);
+    }
+
+    #[tokio::test]
+    async fn keeps_the_zone_given() {
+        let mut query = query(59.91, 10.75);
+        query.timezone = Some(Tz::UTC);
+
+        let response = OfflineProvider.get_prayer_times(&query).await.unwrap();
+        assert_eq!(response.location.timezone, Tz::UTC);
+    }
+
+    #[tokio::test]
+    async fn falls_back_to_the_system_zone_far_from_any_city() {
+        let response = OfflineProvider
+            .get_prayer_times(&query(-40.0, -130.0))
+            .await
+            .unwrap();
+
+        assert_eq!(
+            response.location.timezone,
+            system_timezone().unwrap_or(Tz::UTC)
+        );
+    }
+}
@@ -0,0 +1,148 @@
+use super::astronomy::{darctan2, dcos, dsin, dtan, fix_angle};
+
+pub const KAABA_LATITUDE: f64 = 21.4225;
+pub const KAABA_LONGITUDE: f64 = 39.8262;
+
+/// Initial great-circle bearing from the given point to the Kaaba, in
+/// degrees clockwise from true north
+pub fn qibla_direction(latitude: f64, longitude: f64) -> f64 {
+    let delta_longitude = KAABA_LONGITUDE - longitude;
+
+    let bearing = darctan2(
+        dsin(delta_longitude),
+        dcos(latitude) * dtan(KAABA_LATITUDE) - dsin(latitude) * dcos(delta_longitude),
+    );
+
+    fix_angle(bearing)
+}
+
+/// Mean radius of the Earth in kilometers
+pub const EARTH_RADIUS_KM: f64 = 6371.0088;
+
+/// Great-circle distance between two points in kilometers, by the
+/// haversine formula
+pub fn great_circle_distance(
+    (latitude, longitude): (f64, f64),
+    (other_latitude, other_longitude): (f64, f64),
+) -> f64 {
+    let half_delta_latitude = (other_latitude - latitude) / 2.0;
+    let half_delta_longitude = (other_longitude - longitude) / 2.0;
+
+    let a = dsin(half_delta_latitude).powi(2)
+        + dcos(latitude) * dcos(other_latitude) * dsin(half_delta_longitude).powi(2);
+
+    2.0 * EARTH_RADIUS_KM * a.sqrt().min(1.0).asin()
+}
+
+/// Great-circle distance from the given point to the Kaaba in kilometers
+pub fn kaaba_distance(latitude: f64, longitude: f64) -> f64 {
+    great_circle_distance((latitude, longitude), (KAABA_LATITUDE, KAABA_LONGITUDE))
+}
+
+/// Direction of the Kaaba from a location, computed locally
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Qibla {
+    /// Initial great-circle bearing, in degrees clockwise from true north
+    pub bearing: f64,
+    /// Great-circle distance in kilometers
+    pub distance: f64,
+}
+
+impl Qibla {
+    pub fn at(latitude: f64, longitude: f64) -> Qibla {
+        Qibla {
+            bearing: qibla_direction(latitude, longitude),
+            distance: kaaba_distance(latitude, longitude),
+        }
+    }
+
+    /// Point of the 16 wind compass closest to the bearing, e.g. `ENE`
+    pub fn cardinal(&self) -> &'static str {
+        const POINTS: [&str; 16] = [
+            "N", "NNE", "NE", "ENE", "E", "ESE", "SE", "SSE", "S", "SSW", "SW", "WSW", "W", "WNW",
+            "NW", "NNW",
+        ];
+
+        POINTS[(fix_angle(self.bearing + 11.25) / 22.5) as usize % 16]
+    }
+
+    /// Smallest angle between the bearing and `bearing`, from 0 to 180
+    /// degrees
+    pub fn deviation(&self, bearing: f64) -> f64 {
+        let difference = fix_angle(self.bearing - bearing);
+        difference.min(360.0 - difference)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn assert_near(value: f64, expected: f64, tolerance: f64) {
+        assert!(
+            (value - expected).abs() <= tolerance,
+            "{value} is not within {tolerance} of {expected}"
+        );
+    }
+
+    fn at_bearing(bearing: f64) -> Qibla {
+        Qibla {
+            bearing,
+            distance: 0.0,
+        }
+    }
+
+    #[test]
+    fn points_to_the_kaaba() {
+        let new_york = Qibla::at(40.7128, -74.0060);
+        assert_near(new_york.bearing, 58.48, 0.1);
+        assert_near(new_york.distance, 10_300.0, 50.0);
+        assert_eq!(new_york.cardinal(), "ENE");
+
+        let algiers = Qibla::at(36.7538, 3.0588);
+        assert_near(algiers.bearing, 105.41, 0.1);
+        assert_near(algiers.distance, 3_926.0, 50.0);
+        assert_eq!(algiers.cardinal(), "ESE");
+
+        let jakarta = Qibla::at(-6.2088, 106.8456);
+        assert_near(jakarta.bearing, 295.15, 0.1);
+        assert_near(jakarta.distance, 7_920.0, 50.0);
+        assert_eq!(jakarta.cardinal(), "WNW");
+
+        assert_near(kaaba_distance(KAABA_LATITUDE, KAABA_LONGITUDE), 0.0, 1e-9);
+    }
+
+    #[test]
+    fn measures_great_circle_distances() {
+        let london = (51.5074, -0.1278);
+        let paris = (48.8566, 2.3522);
+        assert_near(great_circle_distance(london, paris), 343.5, 1.0);
+        assert_near(great_circle_distance(paris, london), 343.5, 1.0);
+        assert_near(
+            great_circle_distance((0.0, 0.0), (0.0, 180.0)),
+            std::f64::consts::PI * EARTH_RADIUS_KM,
+            1e-6,
+        );
+    }
+
+    #[test]
+    fn names_the_closest_compass_point() {
+        assert_eq!(at_bearing(0.0).cardinal(), "N");
+        assert_eq!(at_bearing(11.24).cardinal(), "N");
+        assert_eq!(at_bearing(11.25).cardinal(), "NNE");
+        assert_eq!(at_bearing(348.74).cardinal(), "NNW");
+        assert_eq!(at_bearing(348.75).cardinal(), "N");
+        assert_eq!(at_bearing(359.99).cardinal(), "N");
+        assert_eq!(at_bearing(180.0).cardinal(), "S");
+        assert_eq!(at_bearing(-90.0).cardinal(), "W");
+    }
+
+    #[test]
+    fn measures_deviations_across_north() {
+        assert_near(at_bearing(350.0).deviation(10.0), 20.0, 1e-9);
+        assert_near(at_bearing(10.0).deviation(350.0), 20.0, 1e-9);
+        assert_near(at_bearing(90.0).deviation(270.0), 180.0, 1e-9);
+        assert_near(at_bearing(58.0).deviation(58.0), 0.0, 1e-9);
+        assert_near(at_bearing(30.0).deviation(-30.0), 60.0, 1e-9);
+    }
+}
@@ -1,10 +1,28 @@
-use std::str::FromStr;
+use std::{fs, path::PathBuf, str::FromStr, time::Duration};
 
-use clap::{Parser, Subcommand, arg, command};
+use chrono::TimeDelta;
+use clap::{Args, Parser, Subcommand};
 
+#[cfg(feature = "audio")]
+use crate::audio::{AdhanPlayer, AdhanSounds, AudioOutput, PlaybackSettings, expand_home};
 use crate::{
-    api::{self, AdhanError, PrayerTimesPeriod},
-    cli::ui,
+    api::{
+        AdhanError, Lookup, ParsedPrayerTimesResponse, PrayerTimesPeriod, PrayerTimesProvider,
+        PrayerTimesQuery, ProviderRegistry, Tuning, parse_timezone,
+    },
+    calc::{AsrSchool, CalculationMethod, Coordinates, HighLatitudeRule},
+    cli::{
+        config::{Config, Profile},
+        ics::{self, EventDurations, IcsOptions},
+        output::{self, OutputFormat},
+        picker,
+        theme::{DisplaySettings, Theme, TimeFormat},
+        ui,
+    },
+    daemon::{
+        self, DbusNotifier, EventKind, Hook, HookRunner, Notifier, ScheduleSettings, StdoutNotifier,
+    },
+    gazetteer::{self, City, Resolution},
 };
 
 #[derive(Parser)]
@@ -12,27 +30,739 @@ use crate::{
 #[command(version = "0.5")]
 #[command(about = "Adhan Cli, Shows all prayers")]
 struct Cli {
+    /// Profile of the configuration file to use, its default settings when
+    /// not given
+    #[arg(long, global = true)]
+    profile: Option<String>,
     #[command(subcommand)]
     command: Command,
 }
 
 #[derive(Subcommand)]
 enum Command {
-    Show { city: String, period: String },
+    Show {
+        /// City to show, the configured location when not given
+        city: Option<String>,
+        /// daily, weekly, monthly or yearly, the configured period or daily
+        /// when not given
+        period: Option<String>,
+        /// Print the prayer times as json, csv, tsv or table instead of
+        /// opening the interface
+        #[arg(long)]
+        output: Option<String>,
+        #[command(flatten)]
+        display: DisplayArgs,
+        #[command(flatten)]
+        lookup: LookupArgs,
+        #[cfg(feature = "audio")]
+        #[command(flatten)]
+        audio: AudioArgs,
+    },
+    /// Prints the next prayer and the time left before it, for scripts and
+    /// status bars
+    Next {
+        /// City to look up, the configured location when not given
+        city: Option<String>,
+        /// Output template, {name}, {time} and {remaining} are replaced
+        #[arg(long, default_value = "{name} {time} ({remaining})")]
+        format: String,
+        /// How {time} is written: 12h or 24h
+        #[arg(long)]
+        time_format: Option<String>,
+        #[command(flatten)]
+        lookup: LookupArgs,
+    },
+    /// Exports the prayer times to a file format other programs read
+    Export {
+        #[command(subcommand)]
+        format: ExportFormat,
+    },
+    /// Stays in the background and sends a desktop notification at each
+    /// prayer
+    Daemon {
+        /// City to follow, the configured location when not given
+        city: Option<String>,
+        /// Minutes before each prayer for an extra notification
+        #[arg(long)]
+        pre_alert: Option<u32>,
+        /// Minutes between each prayer and its iqamah, e.g. "fajr:20,maghrib:5"
+        #[arg(long)]
+        iqamah: Option<String>,
+        /// Also notify when the time of each prayer is over
+        #[arg(long)]
+        end_of_window: bool,
+        /// Shell command run on a prayer event, as <event>[:<prayer>]=<command>
+        /// with the event one of pre-alert, at-time, iqamah or end-of-window.
+        /// Can be repeated
+        #[arg(long = "hook")]
+        hooks: Vec<String>,
+        /// Seconds after which a hook still running is killed
+        #[arg(long, default_value_t = 30)]
+        hook_timeout: u64,
+        /// D-Bus address of the notification server, the session bus when
+        /// not given
+        #[arg(long)]
+        bus_address: Option<String>,
+        /// Print the notifications instead of sending them over D-Bus
+        #[arg(long)]
+        stdout: bool,
+        #[command(flatten)]
+        lookup: LookupArgs,
+        #[cfg(feature = "audio")]
+        #[command(flatten)]
+        audio: AudioArgs,
+    },
+    /// Lists the cities of the bundled list matching a name, in any of the
+    /// spellings or languages it knows
+    Locate {
+        query: String,
+        /// Most cities listed
+        #[arg(long, default_value_t = 10)]
+        limit: usize,
+    },
+    /// Reads and edits the configuration file
+    Config {
+        #[command(subcommand)]
+        action: ConfigAction,
+    },
+}
+
+#[derive(Subcommand)]
+enum ConfigAction {
+    /// Writes a configuration file listing every setting
+    Init {
+        /// Replace the configuration file when it exists
+        #[arg(long)]
+        force: bool,
+    },
+    /// Prints the path and content of the configuration file
+    Show,
+    /// Sets a setting of the default settings, or of the profile given with
+    /// --profile. An empty value removes it
+    Set { key: String, value: String },
+    /// Prints a setting of the default settings, or of the profile given with
+    /// --profile
+    Get { key: String },
+}
+
+#[derive(Subcommand)]
+enum ExportFormat {
+    /// iCalendar file with an event per prayer, for calendar applications
+    Ics {
+        /// City to export, the configured location when not given
+        city: Option<String>,
+        /// daily, weekly, monthly or yearly, the configured period or daily
+        /// when not given
+        period: Option<String>,
+        /// Event length in minutes, for all prayers and per prayer, e.g.
+        /// "20,fajr:30,maghrib:10"
+        #[arg(long, default_value = "20")]
+        duration: String,
+        /// Minutes before each prayer to show a reminder
+        #[arg(long)]
+        alarm: Option<u32>,
+        /// File to write, stdout when not given
+        #[arg(short, long)]
+        out: Option<PathBuf>,
+        #[command(flatten)]
+        lookup: LookupArgs,
+    },
+}
+
+/// Options shared by the commands looking up prayer times
+#[derive(Args)]
+struct LookupArgs {
+    /// Where the times come from: muslimsalat, aladhan or offline. Defaults to
+    /// offline when coordinates are given, muslimsalat otherwise
+    #[arg(long)]
+    provider: Option<String>,
+    /// Latitude of the location, looked up instead of the city by the
+    /// aladhan and offline providers
+    #[arg(long, requires = "lon", allow_hyphen_values = true)]
+    lat: Option<f64>,
+    /// Longitude of the location, looked up instead of the city by the
+    /// aladhan and offline providers
+    #[arg(long, requires = "lat", allow_hyphen_values = true)]
+    lon: Option<f64>,
+    /// Elevation of the location in meters, 0 when not given
+    #[arg(long)]
+    elevation: Option<f64>,
+    /// Calculation method: mwl, isna, egyptian, umm-al-qura, karachi, tehran,
+    /// gulf, kuwait, qatar, singapore, turkey or custom:<fajr>,<isha>
+    #[arg(long)]
+    method: Option<String>,
+    /// Asr juristic school: shafii (shadow factor 1) or hanafi (shadow factor 2)
+    #[arg(long)]
+    asr: Option<String>,
+    /// Fajr and Isha rule where twilight never ends: middle-of-the-night,
+    /// one-seventh, angle-based or nearest-latitude
+    #[arg(long)]
+    high_latitude: Option<String>,
+    /// Minutes added to each prayer, e.g. "fajr:+2,dhuhr:+3,maghrib:+1"
+    #[arg(long, allow_hyphen_values = true)]
+    tune: Option<String>,
+    /// IANA time zone of the location, e.g. "Africa/Algiers". Defaults to the
+    /// zone reported by the provider, or offline to that of the nearest
+    /// bundled city and then the system zone
+    #[arg(long)]
+    timezone: Option<String>,
+}
+
+/// Appearance of the interface
+#[derive(Args)]
+struct DisplayArgs {
+    /// Colors of the interface: default, light or mono
+    #[arg(long)]
+    theme: Option<String>,
+    /// How times are written: 12h or 24h
+    #[arg(long)]
+    time_format: Option<String>,
+}
+
+impl DisplayArgs {
+    /// Appearance given by the options, or else by `profile`
+    fn resolve(self, profile: &Profile) -> Result<DisplaySettings, AdhanError> {
+        let theme = self
+            .theme
+            .or_else(|| profile.theme.clone())
+            .as_deref()
+            .map(Theme::from_str)
+            .transpose()?
+            .unwrap_or_default();
+
+        Ok(DisplaySettings {
+            theme,
+            time_format: resolve_time_format(self.time_format, profile)?,
+        })
+    }
+}
+
+fn resolve_time_format(
+    time_format: Option<String>,
+    profile: &Profile,
+) -> Result<TimeFormat, AdhanError> {
+    Ok(time_format
+        .or_else(|| profile.time_format.clone())
+        .as_deref()
+        .map(TimeFormat::from_str)
+        .transpose()?
+        .unwrap_or_default())
+}
+
+/// Splits the positional arguments into a city and a period, so that
+/// `show weekly` shows the configured city. The period falls back to the
+/// one of `profile`, then to daily
+fn city_and_period(
+    city: Option<String>,
+    period: Option<String>,
+    profile: &Profile,
+) -> Result<(Option<String>, PrayerTimesPeriod), AdhanError> {
+    let (city, period) = match (city, period) {
+        (Some(city), None) if PrayerTimesPeriod::from_str(&city).is_ok() => (None, Some(city)),
+        arguments => arguments,
+    };
+    let period = match period.or_else(|| profile.period.clone()) {
+        Some(period) => PrayerTimesPeriod::from_str(&period)?,
+        None => PrayerTimesPeriod::Daily,
+    };
+
+    Ok((city, period))
+}
+
+/// Adhan playback options
+#[cfg(feature = "audio")]
+#[derive(Args)]
+struct AudioArgs {
+    /// Audio file played at each prayer
+    #[arg(long)]
+    sound: Option<PathBuf>,
+    /// Audio file for a single prayer, e.g. "fajr=fajr.mp3". Can be repeated
+    #[arg(long = "sound-for")]
+    sound_for: Vec<String>,
+    /// Volume between 0 and 1
+    #[arg(long, default_value_t = 1.0)]
+    volume: f32,
+    /// Seconds the adhan takes to reach its volume
+    #[arg(long, default_value_t = 2.0)]
+    fade: f32,
+    /// Where the sound goes: device, null or file:<path> to write a WAV file
+    #[arg(long, default_value = "device")]
+    audio_output: String,
+}
+
+#[cfg(feature = "audio")]
+impl AudioArgs {
+    /// Starts the player, `None` when no audio file is given
+    fn player(self) -> Result<Option<AdhanPlayer>, AdhanError> {
+        let mut sounds = AdhanSounds {
+            default: self.sound.as_deref().map(expand_home),
+            ..Default::default()
+        };
+        for sound in &self.sound_for {
+            sounds.set_from_str(sound)?;
+        }
+        if sounds.is_empty() {
+            return Ok(None);
+        }
+
+        let settings = PlaybackSettings {
+            volume: self.volume.clamp(0.0, 1.0),
+            fade_in: Duration::from_secs_f32(self.fade.max(0.0)),
+        };
+        let output = AudioOutput::from_str(&self.audio_output)?;
+
+        Ok(Some(AdhanPlayer::spawn(sounds, settings, output)?))
+    }
+}
+
+/// Looks `name` up in the bundled city list. When several cities match,
+/// the user chooses with the picker when given, or else the name is left to
+/// the provider when it looks names up
+fn find_city(
+    name: &str,
+    picker: Option<&Theme>,
+    provider: &dyn PrayerTimesProvider,
+) -> Result<Option<&'static City>, AdhanError> {
+    let candidates = match gazetteer::resolve(name) {
+        Resolution::Found(city) => return Ok(Some(city)),
+        Resolution::NotFound => return Ok(None),
+        Resolution::Ambiguous(candidates) => candidates,
+    };
+    let ambiguous = || {
+        AdhanError::AmbiguousCity(
+            name.to_string(),
+            candidates
+                .iter()
+                .map(|candidate| candidate.city.display_name())
+                .collect(),
+        )
+    };
+
+    match picker {
+        Some(theme) => picker::pick(name, &candidates, theme)?
+            .map(Some)
+            .ok_or_else(ambiguous),
+        None if provider.accepts_names() => Ok(None),
+        None => Err(ambiguous()),
+    }
+}
+
+impl LookupArgs {
+    /// Resolves the provider and query for the prayer times of `city` for
+    /// `period`, the options not given taken from `profile`. An ambiguous
+    /// city is chosen with a picker of the `picker` theme when given, and
+    /// otherwise left to the provider to look up
+    fn resolve(
+        self,
+        city: Option<String>,
+        period: PrayerTimesPeriod,
+        profile: &Profile,
+        picker: Option<&Theme>,
+    ) -> Result<Lookup, AdhanError> {
+        // The location is replaced as a whole, so that a city given on the
+        // command line is not mixed with configured coordinates
+        let (city, lat, lon, elevation) = if city.is_none() && self.lat.is_none() {
+            (
+                profile.city.clone(),
+                profile.lat,
+                profile.lon,
+                profile.elevation,
+            )
+        } else {
+            (city, self.lat, self.lon, self.elevation)
+        };
+
+        let method = self
+            .method
+            .or_else(|| profile.method.clone())
+            .as_deref()
+            .map(CalculationMethod::from_str)
+            .transpose()?;
+        let asr_school = self
+            .asr
+            .or_else(|| profile.asr.clone())
+            .as_deref()
+            .map(AsrSchool::from_str)
+            .transpose()?;
+        let high_latitude_rule = self
+            .high_latitude
+            .or_else(|| profile.high_latitude.clone())
+            .as_deref()
+            .map(HighLatitudeRule::from_str)
+            .transpose()?;
+        let tuning = self
+            .tune
+            .or_else(|| profile.tune.clone())
+            .as_deref()
+            .map(Tuning::from_str)
+            .transpose()?;
+        let mut timezone = self
+            .timezone
+            .or_else(|| profile.timezone.clone())
+            .as_deref()
+            .map(parse_timezone)
+            .transpose()?;
+
+        let mut coordinates = match (lat, lon) {
+            (Some(latitude), Some(longitude)) => Some(Coordinates {
+                latitude,
+                longitude,
+                elevation: elevation.unwrap_or(0.0),
+            }),
+            _ => None,
+        };
+
+        let registry = ProviderRegistry::default();
+        let provider = match self.provider.or_else(|| profile.provider.clone()) {
+            Some(id) => registry.get(&id)?,
+            None if coordinates.is_some() => registry.get("offline")?,
+            None => registry.get("muslimsalat")?,
+        };
+
+        // The bundled list gives the coordinates and zone of a city to the
+        // providers taking them, without any network access
+        let mut city = city;
+        let mut location = None;
+        if coordinates.is_none()
+            && provider.accepts_coordinates()
+            && let Some(name) = &city
+            && let Some(found) = find_city(name, picker, provider.as_ref())?
+        {
+            coordinates = Some(Coordinates {
+                elevation: elevation.unwrap_or(found.elevation),
+                ..found.coordinates()
+            });
+            timezone = timezone.or(Some(found.timezone));
+            city = Some(found.name.to_string());
+            location = Some(found.location());
+        }
+
+        let city = match (city, &coordinates) {
+            (Some(city), _) => city,
+            (None, Some(_)) if !provider.accepts_coordinates() => {
+                return Err(AdhanError::CoordinatesUnsupported(provider.name()));
+            }
+            (None, Some(coordinates)) => {
+                format!("{:.4}, {:.4}", coordinates.latitude, coordinates.longitude)
+            }
+            (None, None) => return Err(AdhanError::LocationRequired),
+        };
+
+        let query = PrayerTimesQuery {
+            city,
+            coordinates,
+            period,
+            date: None,
+            method,
+            asr_school,
+            high_latitude_rule,
+            timezone,
+            location,
+        };
+
+        Ok(Lookup {
+            provider,
+            query,
+            tuning,
+        })
+    }
+
+    /// Fetches the prayer times of `city` for `period`, with the tuning
+    /// applied
+    async fn fetch(
+        self,
+        city: Option<String>,
+        period: PrayerTimesPeriod,
+        profile: &Profile,
+        picker: Option<&Theme>,
+    ) -> Result<ParsedPrayerTimesResponse, AdhanError> {
+        self.resolve(city, period, profile, picker)?.fetch().await
+    }
+}
+
+fn run_config(
+    action: ConfigAction,
+    path: Option<PathBuf>,
+    config: Result<Config, AdhanError>,
+    profile: Option<&str>,
+) -> Result<(), AdhanError> {
+    let path = path.ok_or_else(|| {
+        AdhanError::Config("no configuration directory, set ADHAN_CLI_CONFIG".to_string())
+    })?;
+
+    match action {
+        ConfigAction::Init { force } => {
+            if path.exists() && !force {
+                return Err(AdhanError::Config(format!(
+                    "{} already exists, pass --force to replace it",
+                    path.display()
+                )));
+            }
+            Config::init(&path)?;
+            println!("Wrote {}", path.display());
+        }
+        ConfigAction::Show => {
+            println!("# {}", path.display());
+            match fs::read_to_string(&path) {
+                Ok(content) => output::write_stdout(&content)?,
+                Err(error) if error.kind() == std::io::ErrorKind::NotFound => {
+                    println!("# not created yet, run config init");
+                }
+                Err(error) => return Err(error.into()),
+            }
+        }
+        ConfigAction::Set { key, value } => {
+            let mut config = config?;
+            config.profile_mut(profile).set(&key, &value)?;
+            config.save(&path)?;
+        }
+        ConfigAction::Get { key } => {
+            let config = config?;
+            if let Some(value) = config.profile(profile)?.get(&key)? {
+                println!("{value}");
+            }
+        }
+    }
+
+    Ok(())
 }
 
 pub async fn init() -> Result<(), AdhanError> {
+    // Read before the arguments so that a broken file only fails the
+    // commands that use it
+    let path = Config::path();
+    let config = Config::load(path.as_ref());
+
     let cli = Cli::parse();
+    let profile_name = cli.profile.as_deref();
+    let load_profile = |config: Result<Config, AdhanError>| config?.profile(profile_name);
+
     match cli.command {
-        Command::Show { city, period } => {
-            print!("Fetching");
-            let period = PrayerTimesPeriod::from_str(&period)?;
-            let data = api::get_prayer_data_by_city(&city, period).await?;
-            let parsed = data.parse()?;
-            print!("UI");
-            let _ = ui::entry(parsed);
+        Command::Show {
+            city,
+            period,
+            output,
+            display,
+            lookup,
+            #[cfg(feature = "audio")]
+            audio,
+        } => {
+            let profile = load_profile(config)?;
+            let (city, period) = city_and_period(city, period, &profile)?;
+            let output = output.as_deref().map(OutputFormat::from_str).transpose()?;
+
+            if let Some(format) = output {
+                let parsed = lookup.fetch(city, period, &profile, None).await?;
+                output::print(&output::render(&parsed, format)?)?;
+                return Ok(());
+            }
+
+            let display = display.resolve(&profile)?;
+            let lookup = lookup.resolve(city, period, &profile, Some(&display.theme))?;
+            let parsed = lookup.fetch().await?;
+
+            #[cfg(feature = "audio")]
+            let player = audio.player()?;
+
+            ui::entry(
+                parsed,
+                lookup,
+                display,
+                #[cfg(feature = "audio")]
+                player,
+            )?;
+        }
+        Command::Next {
+            city,
+            format,
+            time_format,
+            lookup,
+        } => {
+            let profile = load_profile(config)?;
+            let time_format = resolve_time_format(time_format, &profile)?;
+            let mut lookup = lookup.resolve(city, PrayerTimesPeriod::Daily, &profile, None)?;
+            let mut parsed = lookup.fetch().await?;
+
+            let now = parsed.now();
+            let today = parsed
+                .today_index(&now)
+                .ok_or(AdhanError::TodayNotCovered)?;
+
+            // Tomorrow's Fajr is only needed once today's Isha is past
+            if today + 1 == parsed.items.len() && now >= parsed.items[today].isha {
+                lookup.query.date = Some(parsed.items[today].date + TimeDelta::days(1));
+                let tomorrow = lookup.fetch().await?;
+                parsed.items.extend(tomorrow.items);
+            }
+            let (prayer, time) = parsed.next_prayer(today, &now);
+
+            println!(
+                "{}",
+                output::render_next(&format, prayer, time, now, time_format)
+            );
+        }
+        Command::Daemon {
+            city,
+            pre_alert,
+            iqamah,
+            end_of_window,
+            hooks,
+            hook_timeout,
+            bus_address,
+            stdout,
+            lookup,
+            #[cfg(feature = "audio")]
+            audio,
+        } => {
+            let profile = load_profile(config)?;
+            // A week holds tomorrow's Fajr, the cache keeping it fresh
+            let lookup = lookup.resolve(city, PrayerTimesPeriod::Weekly, &profile, None)?;
+            let hooks = hooks
+                .iter()
+                .map(|hook| Hook::from_str(hook))
+                .collect::<Result<Vec<_>, _>>()?;
+            let hooks = HookRunner::new(hooks, Duration::from_secs(hook_timeout));
+            let settings = ScheduleSettings {
+                pre_alert,
+                iqamah: iqamah
+                    .as_deref()
+                    .map(Tuning::from_str)
+                    .transpose()?
+                    .unwrap_or_default(),
+                end_of_window: end_of_window || hooks.uses(EventKind::EndOfWindow),
+            };
+            hooks.check(&settings)?;
+
+            let mut notifiers: Vec<Box<dyn Notifier>> = Vec::new();
+            if stdout {
+                notifiers.push(Box::new(StdoutNotifier));
+            } else {
+                notifiers.push(Box::new(
+                    DbusNotifier::connect(bus_address.as_deref()).await?,
+                ));
+            }
+            #[cfg(feature = "audio")]
+            if let Some(player) = audio.player()? {
+                notifiers.push(Box::new(player));
+            }
+
+            daemon::run(&lookup, &settings, &notifiers, &hooks).await?;
+        }
+        Command::Export {
+            format:
+                ExportFormat::Ics {
+                    city,
+                    period,
+                    duration,
+                    alarm,
+                    out,
+                    lookup,
+                },
+        } => {
+            let profile = load_profile(config)?;
+            let (city, period) = city_and_period(city, period, &profile)?;
+            let options = IcsOptions {
+                durations: EventDurations::from_str(&duration)?,
+                alarm,
+            };
+
+            let parsed = lookup.fetch(city, period, &profile, None).await?;
+            let calendar = ics::render(&parsed, &options);
+
+            match out {
+                Some(path) => fs::write(path, calendar)?,
+                None => output::write_stdout(&calendar)?,
+            }
         }
+        Command::Locate { query, limit } => {
+            let matches = gazetteer::search(&query, limit);
+            if matches.is_empty() {
+                return Err(AdhanError::UnknownCity(query));
+            }
+
+            let names: Vec<String> = matches
+                .iter()
+                .map(|candidate| candidate.city.display_name())
+                .collect();
+            let width = names.iter().map(|name| name.chars().count()).max();
+            let lines: Vec<String> = matches
+                .iter()
+                .zip(&names)
+                .map(|(candidate, name)| {
+                    let city = candidate.city;
+                    format!(
+                        "{name:<width$}  {:>8.4} {:>9.4}  {:>5} m  {}",
+                        city.latitude,
+                        city.longitude,
+                        city.elevation,
+                        city.timezone.name(),
+                        width = width.unwrap_or_default()
+                    )
+                })
+                .collect();
+            output::print(&lines.join("\n"))?;
+        }
+        Command::Config { action } => run_config(action, path, config, profile_name)?,
     }
 
     Ok(())
 }
+
+#[cfg(test)]
+mod tests {
+    use chrono_tz::Tz;
+
+    use super::*;
+
+    #[derive(Parser)]
+    struct TestCli {
+        #[command(flatten)]
+        lookup: LookupArgs,
+    }
+
+    fn resolve(city: &str, arguments: &[&str]) -> Result<Lookup, AdhanError> {
+        let arguments = std::iter::once("adhan").chain(arguments.iter().copied());
+        TestCli::parse_from(arguments).lookup.resolve(
+            Some(city.to_string()),
+            PrayerTimesPeriod::Daily,
+            &Profile::default(),
+            None,
+        )
+    }
+
+    #[test]
+    fn keeps_the_default_provider_for_bundled_cities() {
+        let lookup = resolve("Algiers", &[]).unwrap();
+        assert_eq!(lookup.provider.id(), "muslimsalat");
+        assert_eq!(lookup.query.city, "Algiers");
+        assert!(lookup.query.coordinates.is_none());
+        assert!(lookup.query.method.is_none());
+    }
+
+    #[test]
+    fn locates_bundled_cities_for_coordinate_providers() {
+        let lookup = resolve("algier", &["--provider", "offline"]).unwrap();
+        assert_eq!(lookup.query.city, "Algiers");
+        assert_eq!(lookup.query.timezone, Some(Tz::Africa__Algiers));
+        let coordinates = lookup.query.coordinates.unwrap();
+        assert_eq!(
+            (coordinates.latitude, coordinates.longitude),
+            (36.7538, 3.0588)
+        );
+    }
+
+    #[test]
+    fn leaves_ambiguous_cities_to_the_provider() {
+        let lookup = resolve("Tripoli", &["--provider", "aladhan"]).unwrap();
+        assert_eq!(lookup.query.city, "Tripoli");
+        assert!(lookup.query.coordinates.is_none());
+
+        let lookup = resolve("Tripoli, Lebanon", &["--provider", "aladhan"]).unwrap();
+        assert_eq!(lookup.query.timezone, Some(Tz::Asia__Beirut));
+
+        assert!(matches!(
+            resolve("Tripoli", &["--provider", "offline"]),
+            Err(AdhanError::AmbiguousCity(..))
+        ));
+    }
+}
@@ -0,0 +1,419 @@
+use std::{collections::BTreeMap, fs, path::PathBuf, str::FromStr};
+
+use serde::{Deserialize, Serialize};
+
+use crate::{
+    api::{AdhanError, PrayerTimesPeriod, ProviderRegistry, Tuning, parse_timezone},
+    calc::{AsrSchool, CalculationMethod, HighLatitudeRule},
+    cli::theme::{Theme, TimeFormat},
+};
+
+/// Written by `config init`, every setting left commented out
+const TEMPLATE: &str = r#"# adhan-cli configuration, options given on the command line take precedence.
+# Settings of [default] apply to every profile, [profiles.<name>] tables are
+# selected with --profile <name> and override them.
+
+[default]
+# city = "algiers"
+# lat = 36.75
+# lon = 3.04
+# elevation = 0
+# timezone = "Africa/Algiers"
+# period = "daily"
+# provider = "offline"
+# method = "mwl"
+# asr = "shafii"
+# high_latitude = "middle-of-the-night"
+# tune = "fajr:+2,maghrib:+1"
+# theme = "default"
+# time_format = "12h"
+
+# [profiles.work]
+# city = "London, United Kingdom"
+# provider = "aladhan"
+# time_format = "24h"
+"#;
+
+/// Settings of a location and how its prayer times are shown, each one
+/// overridden by the matching command line option
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct Profile {
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub city: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub lat: Option<f64>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub lon: Option<f64>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub elevation: Option<f64>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub timezone: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub period: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub provider: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub method: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub asr: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub high_latitude: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub tune: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub theme: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub time_format: Option<String>,
+}
+
+impl Profile {
+    /// This profile with the settings it leaves out taken from `fallback`.
+    /// The location is taken as a whole, a profile naming a city not
+    /// inheriting the coordinates of the fallback
+    pub fn or(self, fallback: &Profile) -> Profile {
+        let location = if self.city.is_none() && self.lat.is_none() && self.lon.is_none() {
+            fallback
+        } else {
+            &self
+        };
+
+        Profile {
+            city: location.city.clone(),
+            lat: location.lat,
+            lon: location.lon,
+            elevation: location.elevation,
+            timezone: self.timezone.or_else(|| fallback.timezone.clone()),
+            period: self.period.or_else(|| fallback.period.clone()),
+            provider: self.provider.or_else(|| fallback.provider.clone()),
+            method: self.method.or_else(|| fallback.method.clone()),
+            asr: self.asr.or_else(|| fallback.asr.clone()),
+            high_latitude: self
+                .high_latitude
+                .or_else(|| fallback.high_latitude.clone()),
+            tune: self.tune.or_else(|| fallback.tune.clone()),
+            theme: self.theme.or_else(|| fallback.theme.clone()),
+            time_format: self.time_format.or_else(|| fallback.time_format.clone()),
+        }
+    }
+
+    /// Fails when the profile has a latitude without a longitude, or the
+    /// other way round
+    pub fn check(&self) -> Result<(), AdhanError> {
+        if self.lat.is_some() != self.lon.is_some() {
+            return Err(AdhanError::Config(
+                "lat and lon go together, set both with config set coordinates <lat>,<lon>"
+                    .to_string(),
+            ));
+        }
+
+        Ok(())
+    }
+
+    pub fn get(&self, key: &str) -> Result<Option<String>, AdhanError> {
+        let value = match key {
+            "city" => self.city.clone(),
+            "coordinates" => self
+                .lat
+                .zip(self.lon)
+                .map(|(lat, lon)| format!("{lat},{lon}")),
+            "lat" => self.lat.map(|lat| lat.to_string()),
+            "lon" => self.lon.map(|lon| lon.to_string()),
+            "elevation" => self.elevation.map(|elevation| elevation.to_string()),
+            "timezone" => self.timezone.clone(),
+            "period" => self.period.clone(),
+            "provider" => self.provider.clone(),
+            "method" => self.method.clone(),
+            "asr" => self.asr.clone(),
+            "high_latitude" => self.high_latitude.clone(),
+            "tune" => self.tune.clone(),
+            "theme" => self.theme.clone(),
+            "time_format" => self.time_format.clone(),
+            _ => return Err(AdhanError::UnknownConfigKey(key.to_string())),
+        };
+
+        Ok(value)
+    }
+
+    /// Sets `key` after checking that `value` is valid for it, an empty
+    /// value removing the setting. The profile is left as it was when the
+    /// change would leave it with half of its coordinates
+    pub fn set(&mut self, key: &str, value: &str) -> Result<(), AdhanError> {
+        let mut changed = self.clone();
+        changed.set_unchecked(key, value)?;
+        changed.check()?;
+
+        *self = changed;
+        Ok(())
+    }
+
+    fn set_unchecked(&mut self, key: &str, value: &str) -> Result<(), AdhanError> {
+        let text = (!value.is_empty()).then(|| value.to_string());
+        let number = |value: &str| {
+            value
+                .parse::<f64>()
+                .map_err(|_| AdhanError::Config(format!("{key} must be a number")))
+        };
+
+        if let Some(value) = &text {
+            match key {
+                "period" => {
+                    PrayerTimesPeriod::from_str(value)?;
+                }
+                "provider" => {
+                    ProviderRegistry::default().get(value)?;
+                }
+                "method" => {
+                    CalculationMethod::from_str(value)?;
+                }
+                "asr" => {
+                    AsrSchool::from_str(value)?;
+                }
+                "high_latitude" => {
+                    HighLatitudeRule::from_str(value)?;
+                }
+                "tune" => {
+                    Tuning::from_str(value)?;
+                }
+                "timezone" => {
+                    parse_timezone(value)?;
+                }
+                "theme" => {
+                    Theme::from_str(value)?;
+                }
+                "time_format" => {
+                    TimeFormat::from_str(value)?;
+                }
+                _ => {}
+            }
+        }
+
+        match key {
+            "city" => self.city = text,
+            "coordinates" => {
+                let coordinates = match text.as_deref() {
+                    Some(value) => {
+                        let (lat, lon) = value.split_once(',').ok_or_else(|| {
+                            AdhanError::Config("coordinates must be <lat>,<lon>".to_string())
+                        })?;
+                        Some((number(lat.trim())?, number(lon.trim())?))
+                    }
+                    None => None,
+                };
+                self.lat = coordinates.map(|(lat, _)| lat);
+                self.lon = coordinates.map(|(_, lon)| lon);
+            }
+            "lat" => self.lat = text.as_deref().map(number).transpose()?,
+            "lon" => self.lon = text.as_deref().map(number).transpose()?,
+            "elevation" => self.elevation = text.as_deref().map(number).transpose()?,
+            "timezone" => self.timezone = text,
+            "period" => self.period = text,
+            "provider" => self.provider = text,
+            "method" => self.method = text,
+            "asr" => self.asr = text,
+            "high_latitude" => self.high_latitude = text,
+            "tune" => self.tune = text,
+            "theme" => self.theme = text,
+            "time_format" => self.time_format = text,
+            _ => return Err(AdhanError::UnknownConfigKey(key.to_string())),
+        }
+
+        Ok(())
+    }
+}
+
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct Config {
+    pub default: Profile,
+    #[serde(skip_serializing_if = "BTreeMap::is_empty")]
+    pub profiles: BTreeMap<String, Profile>,
+}
+
+impl Config {
+    /// `$ADHAN_CLI_CONFIG`, or `$XDG_CONFIG_HOME/adhan-cli/config.toml` and
+    /// the platform equivalent
+    pub fn path() -> Option<PathBuf> {
+        match std::env::var_os("ADHAN_CLI_CONFIG") {
+            Some(path) => Some(PathBuf::from(path)),
+            None => dirs::config_dir().map(|dir| dir.join("adhan-cli").join("config.toml")),
+        }
+    }
+
+    /// Reads the file at `path`, a missing file being an empty configuration
+    pub fn load(path: Option<&PathBuf>) -> Result<Config, AdhanError> {
+        let Some(path) = path else {
+            return Ok(Config::default());
+        };
+
+        match fs::read_to_string(path) {
+            Ok(content) => {
+                let config: Config = toml::from_str(&content)
+                    .map_err(|error| AdhanError::Config(format!("{}: {error}", path.display())))?;
+                config.check()?;
+                Ok(config)
+            }
+            Err(error) if error.kind() == std::io::ErrorKind::NotFound => Ok(Config::default()),
+            Err(error) => Err(error.into()),
+        }
+    }
+
+    /// Fails on the first profile with half of its coordinates
+    fn check(&self) -> Result<(), AdhanError> {
+        self.default.check()?;
+        self.profiles.values().try_for_each(Profile::check)
+    }
+
+    pub fn save(&self, path: &PathBuf) -> Result<(), AdhanError> {
+        let content =
+            toml::to_string_pretty(self).map_err(|error| AdhanError::Config(error.to_string()))?;
+
+        if let Some(dir) = path.parent() {
+            fs::create_dir_all(dir)?;
+        }
+        fs::write(path, content)?;
+
+        Ok(())
+    }
+
+    /// Writes the commented template to `path`
+    pub fn init(path: &PathBuf) -> Result<(), AdhanError> {
+        if let Some(dir) = path.parent() {
+            fs::create_dir_all(dir)?;
+        }
+        fs::write(path, TEMPLATE)?;
+
+        Ok(())
+    }
+
+    /// Settings of the profile `name` completed by the default ones, or the
+    /// default ones alone when `name` is `None`
+    pub fn profile(&self, name: Option<&str>) -> Result<Profile, AdhanError> {
+        match name {
+            Some(name) => {
+                let profile = self
+                    .profiles
+                    .get(name)
+                    .ok_or_else(|| AdhanError::UnknownProfile(name.to_string()))?;
+                Ok(profile.clone().or(&self.default))
+            }
+            None => Ok(self.default.clone()),
+        }
+    }
+
+    /// Table the profile `name` is stored in, created when missing
+    pub fn profile_mut(&mut self, name: Option<&str>) -> &mut Profile {
+        match name {
+            Some(name) => self.profiles.entry(name.to_string()).or_default(),
+            None => &mut self.default,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn profile(entries: &[(&str, &str)]) -> Profile {
+        let mut profile = Profile::default();
+        for (key, value) in entries {
+            profile.set(key, value).unwrap();
+        }
+        profile
+    }
+
+    #[test]
+    fn profiles_override_the_default_settings() {
+        let default = profile(&[
+            ("coordinates", "36.75,3.04"),
+            ("elevation", "25"),
+            ("method", "mwl"),
+            ("theme", "light"),
+        ]);
+        let work = profile(&[("city", "London, United Kingdom"), ("method", "isna")]);
+
+        let merged = work.or(&default);
+        assert_eq!(merged.city.as_deref(), Some("London, United Kingdom"));
+        assert_eq!(merged.method.as_deref(), Some("isna"));
+        assert_eq!(merged.theme.as_deref(), Some("light"));
+        // The location is taken as a whole
+        assert_eq!(
+            (merged.lat, merged.lon, merged.elevation),
+            (None, None, None)
+        );
+
+        let merged = profile(&[("time_format", "24h")]).or(&default);
+        assert_eq!((merged.lat, merged.lon), (Some(36.75), Some(3.04)));
+        assert_eq!(merged.elevation, Some(25.0));
+        assert_eq!(merged.method.as_deref(), Some("mwl"));
+        assert_eq!(merged.time_format.as_deref(), Some("24h"));
+    }
+
+    #[test]
+    fn reads_back_what_is_set() {
+        let mut profile = Profile::default();
+
+        for (key, value) in [
+            ("city", "Algiers"),
+            ("coordinates", "36.75,3.04"),
+            ("elevation", "25"),
+            ("timezone", "Africa/Algiers"),
+            ("period", "weekly"),
+            ("provider", "aladhan"),
+            ("method", "custom:18,17"),
+            ("asr", "hanafi"),
+            ("high_latitude", "one-seventh"),
+            ("tune", "fajr:+2"),
+            ("theme", "mono"),
+            ("time_format", "24h"),
+        ] {
+            profile.set(key, value).unwrap();
+            assert_eq!(profile.get(key).unwrap().as_deref(), Some(value), "{key}");
+        }
+        assert_eq!(profile.get("lat").unwrap().as_deref(), Some("36.75"));
+
+        profile.set("lon", "3.05").unwrap();
+        assert_eq!(
+            profile.get("coordinates").unwrap().as_deref(),
+            Some("36.75,3.05")
+        );
+
+        profile.set("city", "").unwrap();
+        assert_eq!(profile.get("city").unwrap(), None);
+        profile.set("coordinates", "").unwrap();
+        assert_eq!((profile.lat, profile.lon), (None, None));
+    }
+
+    #[test]
+    fn rejects_invalid_values() {
+        let mut profile = Profile::default();
+
+        for (key, value) in [
+            ("provider", "nowhere"),
+            ("method", "custom:nan,17"),
+            ("period", "hourly"),
+            ("timezone", "Mars/Olympus"),
+            ("elevation", "high"),
+            ("coordinates", "36.75"),
+            ("lat", "36.75"),
+        ] {
+            assert!(profile.set(key, value).is_err(), "{key} = {value}");
+        }
+        assert!(matches!(
+            profile.set("colour", "red"),
+            Err(AdhanError::UnknownConfigKey(_))
+        ));
+        assert_eq!(profile, Profile::default());
+    }
+
+    #[test]
+    fn keeps_the_coordinates_together() {
+        let mut profile = profile(&[("coordinates", "36.75,3.04")]);
+
+        assert!(profile.set("lon", "").is_err());
+        assert_eq!((profile.lat, profile.lon), (Some(36.75), Some(3.04)));
+
+        let config: Config = toml::from_str("[default]\nlat = 36.75\n").unwrap();
+        assert!(config.check().is_err());
+    }
+}
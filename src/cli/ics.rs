@@ -0,0 +1,315 @@
+use std::{collections::HashMap, str::FromStr};
+
+use chrono::{DateTime, DurationRound, NaiveDate, NaiveDateTime, Offset, TimeDelta, Utc};
+use chrono_tz::{OffsetComponents, OffsetName, Tz};
+
+use crate::api::{AdhanError, ParsedPrayerTimesResponse, Prayer, localize};
+
+/// Length of the calendar event of each prayer, in minutes
+#[derive(Debug, Clone, PartialEq)]
+pub struct EventDurations {
+    default: u32,
+    prayers: HashMap<Prayer, u32>,
+}
+
+impl EventDurations {
+    pub fn minutes(&self, prayer: &Prayer) -> u32 {
+        self.prayers.get(prayer).copied().unwrap_or(self.default)
+    }
+}
+
+impl FromStr for EventDurations {
+    type Err = AdhanError;
+
+    /// Parses a default number of minutes and `<prayer>:<minutes>` pairs
+    /// separated by commas, such as `20, fajr:30, maghrib:10`
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let mut durations = EventDurations {
+            default: 20,
+            prayers: HashMap::new(),
+        };
+        let parse_minutes = |minutes: &str| {
+            minutes
+                .trim()
+                .parse()
+                .map_err(|_| AdhanError::InvalidDuration)
+        };
+
+        for entry in s
+            .split(',')
+            .map(str::trim)
+            .filter(|entry| !entry.is_empty())
+        {
+            match entry.split_once(':') {
+                Some((prayer, minutes)) => {
+                    let prayer = Prayer::from_str(prayer.trim())?;
+                    durations.prayers.insert(prayer, parse_minutes(minutes)?);
+                }
+                None => durations.default = parse_minutes(entry)?,
+            }
+        }
+
+        Ok(durations)
+    }
+}
+
+/// What goes in each event besides the prayer times
+pub struct IcsOptions {
+    pub durations: EventDurations,
+    /// Minutes before the prayer to show a reminder, none when `None`
+    pub alarm: Option<u32>,
+}
+
+/// Escapes a TEXT value as required by RFC 5545
+fn escape_text(text: &str) -> String {
+    text.replace('\\', "\\\\")
+        .replace(';', "\\;")
+        .replace(',', "\\,")
+        .replace('\n', "\\n")
+}
+
+/// Folds a content line to 75 octets, continuation lines starting with a space
+fn fold_line(line: &str) -> String {
+    let mut folded = String::new();
+    let mut length = 0;
+
+    for c in line.chars() {
+        if length + c.len_utf8() > 75 {
+            folded.push_str("\r\n ");
+            length = 1;
+        }
+        folded.push(c);
+        length += c.len_utf8();
+    }
+
+    folded
+}
+
+fn format_local(datetime: &NaiveDateTime) -> String {
+    datetime.format("%Y%m%dT%H%M%S").to_string()
+}
+
+fn format_offset(seconds: i32) -> String {
+    let sign = if seconds < 0 { '-' } else { '+' };
+    let seconds = seconds.abs();
+    format!("{sign}{:02}{:02}", seconds / 3600, (seconds % 3600) / 60)
+}
+
+/// Offset changes of `timezone` between `start` and `end`, found hour by
+/// hour then to the second within the hour, as the instant of the change
+fn transitions(timezone: &Tz, start: DateTime<Utc>, end: DateTime<Utc>) -> Vec<DateTime<Utc>> {
+    let offset_at = |instant: DateTime<Utc>| {
+        instant
+            .with_timezone(timezone)
+            .offset()
+            .fix()
+            .local_minus_utc()
+    };
+
+    let mut changes = Vec::new();
+    let mut instant = start.duration_trunc(TimeDelta::hours(1)).unwrap_or(start);
+    while instant < end {
+        let next = instant + TimeDelta::hours(1);
+        if offset_at(next) != offset_at(instant) {
+            // Changes such as St. John's happen on the half hour
+            let (mut before, mut after) = (instant, next);
+            while after - before > TimeDelta::seconds(1) {
+                let middle = before + (after - before) / 2;
+                if offset_at(middle) == offset_at(before) {
+                    before = middle;
+                } else {
+                    after = middle;
+                }
+            }
+            changes.push(after);
+        }
+        instant = next;
+    }
+
+    changes
+}
+
+/// VTIMEZONE describing `timezone` from `first` to `last`, so that clients
+/// without the IANA database read the times right
+fn vtimezone(timezone: &Tz, first: NaiveDate, last: NaiveDate) -> Vec<String> {
+    let midnight = |date: NaiveDate| {
+        localize(timezone, date.and_hms_opt(0, 0, 0).unwrap()).with_timezone(&Utc)
+    };
+    let start = midnight(first - TimeDelta::days(1));
+    let end = midnight(last + TimeDelta::days(2));
+
+    let mut lines = vec![
+        "BEGIN:VTIMEZONE".to_string(),
+        format!("TZID:{}", timezone.name()),
+    ];
+
+    let initial = start.with_timezone(timezone);
+    let mut observances = vec![(initial, initial.offset().fix().local_minus_utc())];
+    for change in transitions(timezone, start, end) {
+        let (previous, _) = observances.last().unwrap();
+        let offset_from = previous.offset().fix().local_minus_utc();
+        observances.push((change.with_timezone(timezone), offset_from));
+    }
+
+    for (instant, offset_from) in observances {
+        let offset = instant.offset();
+        let offset_to = offset.fix().local_minus_utc();
+        let kind = if offset.dst_offset().is_zero() {
+            "STANDARD"
+        } else {
+            "DAYLIGHT"
+        };
+        // DTSTART is the wall clock time before the change
+        let local = instant.naive_utc() + TimeDelta::seconds(offset_from as i64);
+
+        lines.push(format!("BEGIN:{kind}"));
+        lines.push(format!("DTSTART:{}", format_local(&local)));
+        lines.push(format!("TZOFFSETFROM:{}", format_offset(offset_from)));
+        lines.push(format!("TZOFFSETTO:{}", format_offset(offset_to)));
+        if let Some(abbreviation) = offset.abbreviation() {
+            lines.push(format!("TZNAME:{abbreviation}"));
+        }
+        lines.push(format!("END:{kind}"));
+    }
+
+    lines.push("END:VTIMEZONE".to_string());
+    lines
+}
+
+/// Renders every prayer of the response as an iCalendar event. The UIDs
+/// only depend on the location, date and prayer, so importing the file
+/// again updates the events instead of duplicating them
+pub fn render(response: &ParsedPrayerTimesResponse, options: &IcsOptions) -> String {
+    let location = &response.location;
+    let timezone = location.timezone;
+    let place = location.place();
+    let stamp = Utc::now().format("%Y%m%dT%H%M%SZ");
+
+    let mut lines = vec![
+        "BEGIN:VCALENDAR".to_string(),
+        "VERSION:2.0".to_string(),
+        "PRODID:-//adhan-cli//Prayer Times//EN".to_string(),
+        "CALSCALE:GREGORIAN".to_string(),
+        format!(
+            "X-WR-CALNAME:{}",
+            escape_text(&format!("Prayer times {place}"))
+        ),
+        format!("X-WR-TIMEZONE:{}", timezone.name()),
+    ];
+    if let (Some(first), Some(last)) = (response.items.first(), response.items.last()) {
+        lines.extend(vtimezone(&timezone, first.date, last.date));
+    }
+
+    for item in &response.items {
+        for prayer in Prayer::all_prayers() {
+            let start = item.get_prayer(&prayer);
+            let end = start + TimeDelta::minutes(options.durations.minutes(&prayer) as i64);
+            let uid = format!(
+                "{}-{}-{:.4},{:.4}@adhan-cli",
+                item.date.format("%Y%m%d"),
+                prayer.name().to_lowercase(),
+                location.latitude,
+                location.longitude
+            );
+            let mut description = format!("{}, {}", response.method_name, place);
+            if let Some(hijri) = &item.hijri {
+                description = format!("{hijri}\n{description}");
+            }
+
+            lines.push("BEGIN:VEVENT".to_string());
+            lines.push(format!("UID:{uid}"));
+            lines.push(format!("DTSTAMP:{stamp}"));
+            lines.push(format!(
+                "DTSTART;TZID={}:{}",
+                timezone.name(),
+                format_local(&start.naive_local())
+            ));
+            lines.push(format!(
+                "DTEND;TZID={}:{}",
+                timezone.name(),
+                format_local(&end.naive_local())
+            ));
+            lines.push(format!(
+                "SUMMARY:{}",
+                escape_text(&format!("{} ({place})", prayer.name()))
+            ));
+            lines.push(format!("LOCATION:{}", escape_text(&place)));
+            lines.push(format!("DESCRIPTION:{}", escape_text(&description)));
+            lines.push("TRANSP:TRANSPARENT".to_string());
+            if let Some(minutes) = options.alarm {
+                lines.push("BEGIN:VALARM".to_string());
+                lines.push("ACTION:DISPLAY".to_string());
+                lines.push(format!("DESCRIPTION:{}", escape_text(prayer.name())));
+                lines.push(format!("TRIGGER:-PT{minutes}M"));
+                lines.push("END:VALARM".to_string());
+            }
+            lines.push("END:VEVENT".to_string());
+        }
+    }
+
+    lines.push("END:VCALENDAR".to_string());
+
+    lines
+        .iter()
+        .map(|line| fold_line(line))
+        .collect::<Vec<_>>()
+        .join("\r\n")
+        + "\r\n"
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn date(year: i32, month: u32, day: u32) -> NaiveDate {
+        NaiveDate::from_ymd_opt(year, month, day).unwrap()
+    }
+
+    /// DTSTART, TZOFFSETFROM and TZOFFSETTO of each observance
+    fn observances(timezone: &Tz) -> Vec<[String; 3]> {
+        let lines = vtimezone(timezone, date(2026, 1, 1), date(2026, 12, 31));
+
+        lines
+            .iter()
+            .enumerate()
+            .filter(|(_, line)| line.starts_with("DTSTART:"))
+            .map(|(index, _)| {
+                [0, 1, 2].map(|field| {
+                    let line = &lines[index + field];
+                    line.split_once(':').unwrap().1.to_string()
+                })
+            })
+            .collect()
+    }
+
+    #[test]
+    fn oslo_changes_from_the_offset_it_had() {
+        assert_eq!(
+            observances(&chrono_tz::Europe::Oslo)[1..],
+            [
+                ["20260329T020000", "+0100", "+0200"].map(String::from),
+                ["20261025T030000", "+0200", "+0100"].map(String::from),
+            ]
+        );
+    }
+
+    #[test]
+    fn st_johns_changes_on_the_half_hour() {
+        assert_eq!(
+            observances(&chrono_tz::America::St_Johns)[1..],
+            [
+                ["20260308T020000", "-0330", "-0230"].map(String::from),
+                ["20261101T020000", "-0230", "-0330"].map(String::from),
+            ]
+        );
+    }
+
+    #[test]
+    fn folds_long_lines() {
+        let line = "DESCRIPTION:".to_string() + &"x".repeat(100);
+        let folded = fold_line(&line);
+
+        assert!(folded.split("\r\n").all(|part| part.len() <= 75));
+        assert_eq!(folded.replace("\r\n ", ""), line);
+    }
+}
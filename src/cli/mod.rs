@@ -1,4 +1,11 @@
+#[allow(clippy::module_inception)]
 mod cli;
+mod config;
+mod ics;
+mod navigation;
+mod output;
+mod picker;
+mod theme;
 mod ui;
 
 pub use cli::*;
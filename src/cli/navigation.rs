@@ -0,0 +1,271 @@
+use chrono::{NaiveDate, TimeDelta};
+use ratatui::crossterm::event::KeyCode;
+
+use crate::api::ParsedPrayerTimeItem;
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum View {
+    /// Qibla compass and the prayers of the selected day
+    Day,
+    /// Timetable with a row per day of the period
+    Table,
+}
+
+impl View {
+    pub fn toggled(self) -> View {
+        match self {
+            View::Day => View::Table,
+            View::Table => View::Day,
+        }
+    }
+}
+
+/// Day shown among the items of the period, and the one of today
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Selection {
+    /// Index of the current day, `None` when the data does not cover it
+    pub today: Option<usize>,
+    /// Index of the day shown
+    pub selected: usize,
+}
+
+impl Selection {
+    /// Starts on `today`, or on the first day when the data does not cover
+    /// it
+    pub fn new(today: Option<usize>) -> Self {
+        Selection {
+            today,
+            selected: today.unwrap_or_default(),
+        }
+    }
+
+    pub fn is_today_selected(&self) -> bool {
+        self.today == Some(self.selected)
+    }
+
+    /// Moves the current day to `today` as the date changes, the selection
+    /// following it when it was on the previous one
+    pub fn set_today(&mut self, today: Option<usize>) {
+        if today == self.today {
+            return;
+        }
+
+        if let Some(index) = today
+            && self.is_today_selected()
+        {
+            self.selected = index;
+        }
+        self.today = today;
+    }
+
+    /// Moves the selected day by `days`, staying within the `len` items
+    pub fn move_by(&mut self, days: isize, len: usize) {
+        let last = len.saturating_sub(1);
+        self.selected = self.selected.saturating_add_signed(days).min(last);
+    }
+
+    /// Applies a navigation key of `view` among `len` items, returning
+    /// whether `code` is one
+    pub fn handle_key(&mut self, code: KeyCode, view: View, len: usize) -> bool {
+        match code {
+            KeyCode::Left | KeyCode::Char('h') => self.move_by(-1, len),
+            KeyCode::Right | KeyCode::Char('l') => self.move_by(1, len),
+            KeyCode::PageUp => self.move_by(-7, len),
+            KeyCode::PageDown => self.move_by(7, len),
+            KeyCode::Char('t') => {
+                if let Some(today) = self.today {
+                    self.selected = today;
+                }
+            }
+            KeyCode::Char('j') | KeyCode::Down if view == View::Table => self.move_by(1, len),
+            KeyCode::Char('k') | KeyCode::Up if view == View::Table => self.move_by(-1, len),
+            KeyCode::Char('g') if view == View::Table => self.selected = 0,
+            KeyCode::Char('G') if view == View::Table => self.selected = len.saturating_sub(1),
+            _ => return false,
+        }
+
+        true
+    }
+}
+
+/// Date to add after the last of `items` once the location has reached
+/// `date`, when today was the last day of the data
+pub fn missing_day(
+    items: &[ParsedPrayerTimeItem],
+    today: Option<usize>,
+    date: NaiveDate,
+) -> Option<NaiveDate> {
+    let last = items.last()?;
+
+    (today? + 1 == items.len() && last.date < date).then(|| last.date + TimeDelta::days(1))
+}
+
+/// Warnings of the header about the data shown for `selected`
+pub fn warnings(
+    selection: &Selection,
+    estimated: &[NaiveDate],
+    selected: NaiveDate,
+) -> Vec<&'static str> {
+    let mut warnings = Vec::new();
+
+    if selection.today.is_none() {
+        warnings.push("data does not cover today");
+    }
+    if estimated.contains(&selected) {
+        warnings.push("estimated from the day before");
+    }
+
+    warnings
+}
+
+#[cfg(test)]
+mod tests {
+    use chrono_tz::Tz;
+
+    use super::*;
+    use crate::calc::{self, CalculationSettings, Coordinates};
+
+    fn items(first: NaiveDate, days: u64) -> Vec<ParsedPrayerTimeItem> {
+        let coordinates = Coordinates {
+            latitude: 36.75,
+            longitude: 3.04,
+            elevation: 0.0,
+        };
+
+        first
+            .iter_days()
+            .take(days as usize)
+            .map(|date| {
+                calc::calculate_prayer_times(
+                    &coordinates,
+                    &CalculationSettings::default(),
+                    date,
+                    &Tz::Africa__Algiers,
+                )
+                .unwrap()
+            })
+            .collect()
+    }
+
+    fn date(day: u32) -> NaiveDate {
+        NaiveDate::from_ymd_opt(2026, 10, day).unwrap()
+    }
+
+    #[test]
+    fn keeps_the_selection_within_the_days() {
+        let mut selection = Selection::new(Some(2));
+
+        selection.move_by(-7, 5);
+        assert_eq!(selection.selected, 0);
+        selection.move_by(7, 5);
+        assert_eq!(selection.selected, 4);
+        selection.move_by(1, 5);
+        assert_eq!(selection.selected, 4);
+
+        selection.move_by(1, 0);
+        assert_eq!(selection.selected, 0);
+    }
+
+    #[test]
+    fn goes_back_to_today() {
+        let mut selection = Selection::new(Some(1));
+
+        assert!(selection.handle_key(KeyCode::PageDown, View::Day, 7));
+        assert_eq!(selection.selected, 6);
+        assert!(!selection.is_today_selected());
+        assert!(selection.handle_key(KeyCode::Char('h'), View::Day, 7));
+        assert_eq!(selection.selected, 5);
+
+        assert!(selection.handle_key(KeyCode::Char('t'), View::Day, 7));
+        assert!(selection.is_today_selected());
+        assert!(!selection.handle_key(KeyCode::Char('x'), View::Day, 7));
+
+        let mut missing = Selection::new(None);
+        missing.move_by(2, 7);
+        assert!(missing.handle_key(KeyCode::Char('t'), View::Day, 7));
+        assert_eq!(missing.selected, 2);
+    }
+
+    #[test]
+    fn scrolls_the_timetable_only_in_its_view() {
+        let mut selection = Selection::new(Some(1));
+
+        assert!(!selection.handle_key(KeyCode::Char('j'), View::Day, 7));
+        assert!(!selection.handle_key(KeyCode::Char('G'), View::Day, 7));
+        assert_eq!(selection.selected, 1);
+
+        assert!(selection.handle_key(KeyCode::Char('j'), View::Table, 7));
+        assert_eq!(selection.selected, 2);
+        assert!(selection.handle_key(KeyCode::Char('G'), View::Table, 7));
+        assert_eq!(selection.selected, 6);
+        assert!(selection.handle_key(KeyCode::Up, View::Table, 7));
+        assert_eq!(selection.selected, 5);
+        assert!(selection.handle_key(KeyCode::Char('g'), View::Table, 7));
+        assert_eq!(selection.selected, 0);
+
+        assert!(selection.handle_key(KeyCode::Char('t'), View::Day, 7));
+        assert_eq!(selection.selected, 1);
+        assert!(!selection.handle_key(KeyCode::Char('x'), View::Table, 7));
+    }
+
+    #[test]
+    fn follows_today_when_it_was_selected() {
+        let mut selection = Selection::new(Some(0));
+        selection.set_today(Some(1));
+        assert_eq!(
+            selection,
+            Selection {
+                today: Some(1),
+                selected: 1
+            }
+        );
+
+        let mut selection = Selection::new(Some(0));
+        selection.move_by(3, 7);
+        selection.set_today(Some(1));
+        assert_eq!(
+            selection,
+            Selection {
+                today: Some(1),
+                selected: 3
+            }
+        );
+
+        selection.set_today(None);
+        assert_eq!(
+            selection,
+            Selection {
+                today: None,
+                selected: 3
+            }
+        );
+        assert!(!selection.is_today_selected());
+    }
+
+    #[test]
+    fn adds_the_day_after_the_data() {
+        let items = items(date(17), 2);
+
+        assert_eq!(missing_day(&items, Some(1), date(18)), None);
+        assert_eq!(missing_day(&items, Some(1), date(19)), Some(date(19)));
+        assert_eq!(missing_day(&items, Some(0), date(19)), None);
+        assert_eq!(missing_day(&items, None, date(19)), None);
+        assert_eq!(missing_day(&[], Some(0), date(19)), None);
+    }
+
+    #[test]
+    fn warns_about_missing_and_estimated_days() {
+        let covered = Selection::new(Some(0));
+        assert!(warnings(&covered, &[], date(17)).is_empty());
+
+        let missing = Selection::new(None);
+        assert_eq!(
+            warnings(&missing, &[date(18)], date(18)),
+            ["data does not cover today", "estimated from the day before"]
+        );
+        assert_eq!(
+            warnings(&covered, &[date(18)], date(17)),
+            Vec::<&str>::new()
+        );
+    }
+}
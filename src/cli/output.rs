@@ -0,0 +1,424 @@
+use std::{
+    io::{self, Write},
+    str::FromStr,
+};
+
+use chrono::{DateTime, NaiveDate};
+use chrono_tz::Tz;
+use serde::Serialize;
+
+use crate::{
+    api::{AdhanError, ParsedPrayerTimeItem, ParsedPrayerTimesResponse, Prayer, format_countdown},
+    cli::theme::TimeFormat,
+};
+
+/// How `show --output` prints the prayer times instead of opening the TUI
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum OutputFormat {
+    Json,
+    Csv,
+    Tsv,
+    Table,
+}
+
+impl FromStr for OutputFormat {
+    type Err = AdhanError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.to_lowercase().as_str() {
+            "json" => Ok(Self::Json),
+            "csv" => Ok(Self::Csv),
+            "tsv" => Ok(Self::Tsv),
+            "table" | "text" => Ok(Self::Table),
+            _ => Err(AdhanError::InvalidOutputFormat),
+        }
+    }
+}
+
+#[derive(Serialize)]
+struct JsonOutput<'a> {
+    city: &'a str,
+    state: &'a str,
+    country: &'a str,
+    timezone: &'a str,
+    latitude: f64,
+    longitude: f64,
+    qibla_direction: f64,
+    period: String,
+    method: &'a str,
+    asr_school: &'a str,
+    high_latitude_rule: Option<&'a str>,
+    items: Vec<JsonItem>,
+}
+
+#[derive(Serialize)]
+struct JsonItem {
+    date: NaiveDate,
+    hijri: Option<String>,
+    fajr: DateTime<Tz>,
+    shurooq: DateTime<Tz>,
+    dhuhr: DateTime<Tz>,
+    asr: DateTime<Tz>,
+    maghrib: DateTime<Tz>,
+    isha: DateTime<Tz>,
+}
+
+impl From<&ParsedPrayerTimeItem> for JsonItem {
+    fn from(item: &ParsedPrayerTimeItem) -> Self {
+        JsonItem {
+            date: item.date,
+            hijri: item.hijri.as_ref().map(|hijri| hijri.to_string()),
+            fajr: item.fajr,
+            shurooq: item.shurooq,
+            dhuhr: item.dhuhr,
+            asr: item.asr,
+            maghrib: item.maghrib,
+            isha: item.isha,
+        }
+    }
+}
+
+/// Writes `text` and a line break to stdout
+pub fn print(text: &str) -> Result<(), AdhanError> {
+    write_stdout(&format!("{text}\n"))
+}
+
+/// Writes `text` as is to stdout, a reader closing the pipe early, as
+/// `head` does, not being an error
+pub fn write_stdout(text: &str) -> Result<(), AdhanError> {
+    match io::stdout().lock().write_all(text.as_bytes()) {
+        Err(error) if error.kind() != io::ErrorKind::BrokenPipe => Err(error.into()),
+        _ => Ok(()),
+    }
+}
+
+/// Fills the `{name}`, `{time}` and `{remaining}` placeholders of
+/// `template` for `prayer` at `time`, other braces being left as they are
+/// for formats such as JSON
+pub fn render_next(
+    template: &str,
+    prayer: Prayer,
+    time: DateTime<Tz>,
+    now: DateTime<Tz>,
+    time_format: TimeFormat,
+) -> String {
+    template
+        .replace("{name}", prayer.name())
+        .replace("{time}", &time.format(time_format.pattern()).to_string())
+        .replace("{remaining}", &format_countdown(time - now))
+}
+
+/// Renders the response in `format`, each item being a row
+pub fn render(
+    response: &ParsedPrayerTimesResponse,
+    format: OutputFormat,
+) -> Result<String, AdhanError> {
+    match format {
+        OutputFormat::Json => render_json(response),
+        OutputFormat::Csv => Ok(render_separated(response, ',')),
+        OutputFormat::Tsv => Ok(render_separated(response, '\t')),
+        OutputFormat::Table => Ok(render_table(response)),
+    }
+}
+
+fn render_json(response: &ParsedPrayerTimesResponse) -> Result<String, AdhanError> {
+    let location = &response.location;
+    let output = JsonOutput {
+        city: &location.city,
+        state: &location.state,
+        country: &location.country,
+        timezone: location.timezone.name(),
+        latitude: location.latitude,
+        longitude: location.longitude,
+        qibla_direction: location.qibla_direction,
+        period: response.period.to_string(),
+        method: &response.method_name,
+        asr_school: response.asr_school.name(),
+        high_latitude_rule: response.high_latitude_rule.map(|rule| rule.name()),
+        items: response.items.iter().map(JsonItem::from).collect(),
+    };
+
+    Ok(serde_json::to_string_pretty(&output)?)
+}
+
+fn header() -> Vec<String> {
+    ["date", "weekday", "hijri"]
+        .into_iter()
+        .map(String::from)
+        .chain(
+            Prayer::all_prayers()
+                .iter()
+                .map(|prayer| prayer.name().to_lowercase()),
+        )
+        .collect()
+}
+
+/// How the prayer times of a row are written
+#[derive(Clone, Copy)]
+enum TimeStyle {
+    /// RFC 3339 date, time and offset, for programs
+    Timestamp,
+    /// Wall clock time, followed by `+1` when on the next day
+    Clock,
+}
+
+impl TimeStyle {
+    fn format(self, item: &ParsedPrayerTimeItem, time: DateTime<Tz>) -> String {
+        match self {
+            Self::Timestamp => time.to_rfc3339(),
+            Self::Clock if time.date_naive() > item.date => format!("{} +1", time.format("%H:%M")),
+            Self::Clock => time.format("%H:%M").to_string(),
+        }
+    }
+}
+
+fn row(item: &ParsedPrayerTimeItem, style: TimeStyle) -> Vec<String> {
+    let hijri = match &item.hijri {
+        Some(hijri) => hijri.to_string(),
+        None => String::new(),
+    };
+
+    [
+        item.date.format("%Y-%m-%d").to_string(),
+        item.date.format("%a").to_string(),
+        hijri,
+    ]
+    .into_iter()
+    .chain(
+        Prayer::all_prayers()
+            .iter()
+            .map(|prayer| style.format(item, item.get_prayer(prayer))),
+    )
+    .collect()
+}
+
+/// Quotes a field containing the separator, a quote or a line break
+fn escape_field(field: &str, separator: char) -> String {
+    if field.contains([separator, '"', '\n', '\r']) {
+        format!("\"{}\"", field.replace('"', "\"\""))
+    } else {
+        field.to_string()
+    }
+}
+
+fn render_separated(response: &ParsedPrayerTimesResponse, separator: char) -> String {
+    std::iter::once(header())
+        .chain(
+            response
+                .items
+                .iter()
+                .map(|item| row(item, TimeStyle::Timestamp)),
+        )
+        .map(|fields| {
+            fields
+                .iter()
+                .map(|field| escape_field(field, separator))
+                .collect::<Vec<_>>()
+                .join(&separator.to_string())
+        })
+        .collect::<Vec<_>>()
+        .join("\n")
+}
+
+fn render_table(response: &ParsedPrayerTimesResponse) -> String {
+    let location = &response.location;
+    let rows: Vec<Vec<String>> = std::iter::once(header())
+        .chain(
+            response
+                .items
+                .iter()
+                .map(|item| row(item, TimeStyle::Clock)),
+        )
+        .collect();
+
+    let widths: Vec<usize> = (0..rows[0].len())
+        .map(|column| {
+            rows.iter()
+                .map(|row| row[column].chars().count())
+                .max()
+                .unwrap_or_default()
+        })
+        .collect();
+
+    let mut lines = vec![
+        format!(
+            "{} {} ({}) | {} | Asr {}",
+            location.country,
+            location.city,
+            location.timezone.name(),
+            response.method_name,
+            response.asr_school.name()
+        )
+        .trim_start()
+        .to_string(),
+    ];
+    lines.extend(rows.iter().map(|row| {
+        row.iter()
+            .zip(&widths)
+            .map(|(field, width)| format!("{field:<width$}"))
+            .collect::<Vec<_>>()
+            .join("  ")
+            .trim_end()
+            .to_string()
+    }));
+
+    lines.join("\n")
+}
+
+#[cfg(test)]
+mod tests {
+    use chrono::{TimeDelta, TimeZone};
+
+    use super::*;
+    use crate::{
+        api::{HijriDate, PrayerTimesPeriod},
+        calc::HighLatitudeRule,
+        calc::{self, AsrSchool, CalculationMethod, CalculationSettings, Coordinates},
+    };
+
+    /// Two days of Oslo around midsummer, when Isha falls after midnight
+    fn oslo() -> ParsedPrayerTimesResponse {
+        let mut response = calc::get_prayer_data_by_coordinates(
+            "Oslo",
+            &Coordinates {
+                latitude: 59.9139,
+                longitude: 10.7522,
+                elevation: 0.0,
+            },
+            &CalculationSettings {
+                method: CalculationMethod::MuslimWorldLeague,
+                asr_school: AsrSchool::Shafii,
+                high_latitude_rule: HighLatitudeRule::AngleBased,
+            },
+            PrayerTimesPeriod::Weekly,
+            NaiveDate::from_ymd_opt(2026, 6, 21).unwrap(),
+            chrono_tz::Europe::Oslo,
+        )
+        .unwrap();
+        response.items.truncate(2);
+        response.items[0].hijri = Some(HijriDate {
+            day: 6,
+            month_name: "Muharram".to_string(),
+            year: 1448,
+        });
+
+        response
+    }
+
+    #[test]
+    fn renders_json() {
+        let json = render(&oslo(), OutputFormat::Json).unwrap();
+        let value: serde_json::Value = serde_json::from_str(&json).unwrap();
+
+        assert_eq!(value["city"], "Oslo");
+        assert_eq!(value["timezone"], "Europe/Oslo");
+        assert_eq!(value["period"], "weekly");
+        assert_eq!(value["high_latitude_rule"], "Angle-based");
+        assert_eq!(value["items"].as_array().unwrap().len(), 2);
+        assert_eq!(value["items"][0]["date"], "2026-06-21");
+        assert_eq!(value["items"][0]["hijri"], "6 Muharram 1448 AH");
+        assert_eq!(value["items"][1]["hijri"], serde_json::Value::Null);
+        assert!(
+            value["items"][0]["isha"]
+                .as_str()
+                .unwrap()
+                .starts_with("2026-06-22T00:")
+        );
+    }
+
+    #[test]
+    fn renders_csv_with_dates_past_midnight() {
+        let csv = render(&oslo(), OutputFormat::Csv).unwrap();
+        let lines: Vec<&str> = csv.lines().collect();
+
+        assert_eq!(lines.len(), 3);
+        assert_eq!(
+            lines[0],
+            "date,weekday,hijri,fajr,shurooq,dhuhr,asr,maghrib,isha"
+        );
+        let fields: Vec<&str> = lines[1].split(',').collect();
+        assert_eq!(fields[..3], ["2026-06-21", "Sun", "6 Muharram 1448 AH"]);
+        assert!(fields[3].starts_with("2026-06-21T") && fields[3].ends_with("+02:00"));
+        assert!(fields[8].starts_with("2026-06-22T00:"), "{}", fields[8]);
+        assert_eq!(lines[2].split(',').nth(2), Some(""));
+    }
+
+    #[test]
+    fn renders_tsv() {
+        let tsv = render(&oslo(), OutputFormat::Tsv).unwrap();
+        let lines: Vec<&str> = tsv.lines().collect();
+
+        assert_eq!(lines.len(), 3);
+        assert!(lines.iter().all(|line| line.split('\t').count() == 9));
+        assert!(lines[1].contains("\t6 Muharram 1448 AH\t"));
+    }
+
+    #[test]
+    fn renders_an_aligned_table() {
+        let table = render(&oslo(), OutputFormat::Table).unwrap();
+        let lines: Vec<&str> = table.lines().collect();
+
+        assert_eq!(lines.len(), 4);
+        assert!(lines[0].starts_with("Oslo (Europe/Oslo) | Muslim World League"));
+        let isha = lines[1].find("isha").unwrap();
+        assert_eq!(lines[2].find("00:").unwrap(), isha);
+        assert!(lines[2].ends_with(" +1"));
+    }
+
+    #[test]
+    fn quotes_fields_with_separators() {
+        assert_eq!(escape_field("6 Muharram", ','), "6 Muharram");
+        assert_eq!(escape_field("a,b", ','), "\"a,b\"");
+        assert_eq!(escape_field("a,b", '\t'), "a,b");
+        assert_eq!(escape_field("say \"hi\"", '\t'), "\"say \"\"hi\"\"\"");
+    }
+
+    fn algiers(hour: u32, minute: u32) -> DateTime<Tz> {
+        chrono_tz::Africa::Algiers
+            .with_ymd_and_hms(2026, 10, 17, hour, minute, 0)
+            .unwrap()
+    }
+
+    #[test]
+    fn fills_the_next_prayer_template() {
+        let time = algiers(17, 5);
+        let now = time - TimeDelta::seconds(3725);
+
+        assert_eq!(
+            render_next(
+                "{name} {time} ({remaining})",
+                Prayer::Maghrib,
+                time,
+                now,
+                TimeFormat::TwentyFourHour
+            ),
+            "Maghrib 17:05 (01:02:05)"
+        );
+        assert_eq!(
+            render_next(
+                "{time}|{time}",
+                Prayer::Fajr,
+                algiers(5, 31),
+                now,
+                TimeFormat::TwelveHour
+            ),
+            "5:31 AM|5:31 AM"
+        );
+    }
+
+    #[test]
+    fn leaves_unknown_placeholders() {
+        let time = algiers(12, 33);
+
+        assert_eq!(
+            render_next(
+                r#"{"text": "{name}", "tooltip": "{date}"}"#,
+                Prayer::Dhuhr,
+                time,
+                time - TimeDelta::minutes(5),
+                TimeFormat::TwentyFourHour
+            ),
+            r#"{"text": "Dhuhr", "tooltip": "{date}"}"#
+        );
+    }
+}
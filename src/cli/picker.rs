@@ -0,0 +1,109 @@
+use std::io;
+
+use ratatui::{
+    Terminal,
+    crossterm::{
+        event::{self, Event, KeyCode, KeyEventKind},
+        execute,
+        terminal::{EnterAlternateScreen, LeaveAlternateScreen, disable_raw_mode, enable_raw_mode},
+    },
+    prelude::CrosstermBackend,
+    style::{Modifier, Style, Stylize},
+    text::Line,
+    widgets::{Block, Borders, HighlightSpacing, List, ListItem, ListState},
+};
+
+use crate::{
+    api::AdhanError,
+    cli::theme::Theme,
+    gazetteer::{City, Match},
+};
+
+fn candidate_line(candidate: &Match) -> String {
+    let city = candidate.city;
+    format!(
+        "{}  ({:.2}, {:.2}, {})",
+        city.display_name(),
+        city.latitude,
+        city.longitude,
+        city.timezone.name()
+    )
+}
+
+/// Lets the user choose among the cities matching `query`, `None` when the
+/// choice is cancelled
+pub fn pick(
+    query: &str,
+    candidates: &[Match],
+    theme: &Theme,
+) -> Result<Option<&'static City>, AdhanError> {
+    enable_raw_mode()?;
+    let mut stdout = io::stdout();
+    execute!(stdout, EnterAlternateScreen)?;
+    let backend = CrosstermBackend::new(stdout);
+    let mut terminal = Terminal::new(backend)?;
+
+    let res = run_picker(&mut terminal, query, candidates, theme);
+
+    disable_raw_mode()?;
+    execute!(terminal.backend_mut(), LeaveAlternateScreen)?;
+    terminal.show_cursor()?;
+
+    Ok(res?)
+}
+
+fn run_picker<B: ratatui::backend::Backend>(
+    terminal: &mut Terminal<B>,
+    query: &str,
+    candidates: &[Match],
+    theme: &Theme,
+) -> io::Result<Option<&'static City>> {
+    let mut state = ListState::default().with_selected(Some(0));
+
+    loop {
+        terminal.draw(|frame| {
+            let items: Vec<ListItem> = candidates
+                .iter()
+                .map(|candidate| ListItem::new(candidate_line(candidate)).fg(theme.item))
+                .collect();
+            let list = List::new(items)
+                .block(
+                    Block::default()
+                        .title(Line::from(format!(" Several cities match \"{query}\" ")).bold())
+                        .title_bottom(" ↑/↓ choose · Enter confirm · Esc cancel ")
+                        .borders(Borders::ALL)
+                        .border_style(Style::default().fg(theme.border)),
+                )
+                .highlight_style(
+                    Style::default()
+                        .fg(theme.highlight_fg)
+                        .bg(theme.highlight_bg)
+                        .add_modifier(Modifier::BOLD),
+                )
+                .highlight_symbol("> ")
+                .highlight_spacing(HighlightSpacing::Always);
+
+            frame.render_stateful_widget(list, frame.area(), &mut state);
+        })?;
+
+        let Event::Key(key) = event::read()? else {
+            continue;
+        };
+        if key.kind != KeyEventKind::Press {
+            continue;
+        }
+
+        match key.code {
+            KeyCode::Down | KeyCode::Char('j') => state.select_next(),
+            KeyCode::Up | KeyCode::Char('k') => state.select_previous(),
+            KeyCode::Enter => {
+                return Ok(state
+                    .selected()
+                    .and_then(|index| candidates.get(index))
+                    .map(|candidate| candidate.city));
+            }
+            KeyCode::Esc | KeyCode::Char('q') => return Ok(None),
+            _ => {}
+        }
+    }
+}
@@ -0,0 +1,99 @@
+use std::str::FromStr;
+
+use ratatui::style::Color;
+
+use crate::api::AdhanError;
+
+/// Colors of the interface
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Theme {
+    pub border: Color,
+    pub text: Color,
+    pub item: Color,
+    pub highlight_fg: Color,
+    pub highlight_bg: Color,
+    pub friday: Color,
+    pub muted: Color,
+}
+
+impl Default for Theme {
+    fn default() -> Self {
+        Theme {
+            border: Color::Cyan,
+            text: Color::Yellow,
+            item: Color::LightCyan,
+            highlight_fg: Color::White,
+            highlight_bg: Color::Magenta,
+            friday: Color::Green,
+            muted: Color::DarkGray,
+        }
+    }
+}
+
+impl FromStr for Theme {
+    type Err = AdhanError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.to_lowercase().as_str() {
+            "default" => Ok(Theme::default()),
+            "light" => Ok(Theme {
+                border: Color::Blue,
+                text: Color::Black,
+                item: Color::Blue,
+                highlight_fg: Color::White,
+                highlight_bg: Color::Magenta,
+                friday: Color::Green,
+                muted: Color::Gray,
+            }),
+            "mono" => Ok(Theme {
+                border: Color::Reset,
+                text: Color::Reset,
+                item: Color::Reset,
+                highlight_fg: Color::Black,
+                highlight_bg: Color::White,
+                friday: Color::Reset,
+                muted: Color::Reset,
+            }),
+            _ => Err(AdhanError::InvalidTheme),
+        }
+    }
+}
+
+/// How times of day are written
+#[derive(Debug, Clone, Copy, PartialEq, Default)]
+pub enum TimeFormat {
+    /// `5:31 PM`
+    #[default]
+    TwelveHour,
+    /// `17:31`
+    TwentyFourHour,
+}
+
+impl TimeFormat {
+    /// `chrono` format string of a time of day
+    pub fn pattern(&self) -> &'static str {
+        match self {
+            Self::TwelveHour => "%-I:%M %p",
+            Self::TwentyFourHour => "%H:%M",
+        }
+    }
+}
+
+impl FromStr for TimeFormat {
+    type Err = AdhanError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.to_lowercase().as_str() {
+            "12h" | "12" => Ok(Self::TwelveHour),
+            "24h" | "24" => Ok(Self::TwentyFourHour),
+            _ => Err(AdhanError::InvalidTimeFormat),
+        }
+    }
+}
+
+/// Appearance of the interface
+#[derive(Debug, Clone, Copy, PartialEq, Default)]
+pub struct DisplaySettings {
+    pub theme: Theme,
+    pub time_format: TimeFormat,
+}
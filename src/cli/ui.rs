@@ -1,4 +1,5 @@
-use chrono::{FixedOffset, Local, NaiveTime, TimeZone, Timelike, Utc};
+use chrono::{DateTime, Datelike, Local, NaiveDate, Weekday};
+use chrono_tz::Tz;
 use ratatui::{
     Frame, Terminal,
     crossterm::{
@@ -8,119 +9,248 @@ use ratatui::{
     },
     layout::{Alignment, Constraint, Direction, Layout, Rect},
     prelude::CrosstermBackend,
-    style::{Color, Modifier, Style, Stylize},
-    symbols::block,
+    style::{Modifier, Style, Stylize},
     text::{Line, Span},
     widgets::{
-        Block, Borders, Chart, GraphType, HighlightSpacing, List, ListItem, Paragraph,
-        canvas::{Canvas, Circle, Line as CanvasLine, Points},
+        Block, Borders, Cell, HighlightSpacing, List, ListItem, Paragraph, Row, Table, TableState,
+        canvas::{Canvas, Circle, Line as CanvasLine},
     },
 };
 
-use crate::api::*;
+#[cfg(feature = "audio")]
+use crate::audio::AdhanPlayer;
+use crate::{
+    api::*,
+    calc::{self, Coordinates},
+    cli::{
+        navigation::{self, Selection, View},
+        theme::DisplaySettings,
+    },
+};
 use std::io;
+use tokio::sync::oneshot;
 
 pub struct App {
     prayer_times: ParsedPrayerTimesResponse,
+    /// Fetches the days that follow the period once it has passed
+    lookup: Lookup,
+    /// Days copied from the one before them, neither fetched nor computed
+    estimated: Vec<NaiveDate>,
+    /// Times of a day added after the period, on their way from the
+    /// provider to replace the ones computed offline
+    refresh: Option<oneshot::Receiver<ParsedPrayerTimeItem>>,
+    selection: Selection,
+    view: View,
+    table_state: TableState,
+    display: DisplaySettings,
+    /// Plays the adhan when the countdown reaches zero
+    #[cfg(feature = "audio")]
+    player: Option<AdhanPlayer>,
+    /// Next prayer as of the previous tick, announced once its time comes
+    #[cfg(feature = "audio")]
+    pending_prayer: Option<(Prayer, DateTime<Tz>)>,
     should_quit: bool,
 }
 
 impl App {
-    fn get_timezone_now(&self) -> NaiveTime {
-        let mut timezone = self.prayer_times.location.timezone as i32;
+    fn find_today(&self) -> Option<usize> {
+        self.prayer_times.today_index(&self.get_timezone_now())
+    }
 
-        if self.prayer_times.location.daylight {
-            timezone = timezone + 1;
+    /// Query of the times of the current day alone
+    fn day_lookup(&self) -> Lookup {
+        Lookup {
+            provider: self.lookup.provider.clone(),
+            query: PrayerTimesQuery {
+                period: PrayerTimesPeriod::Daily,
+                timezone: Some(self.prayer_times.location.timezone),
+                ..self.lookup.query.clone()
+            },
+            tuning: self.lookup.tuning.clone(),
         }
+    }
 
-        let hour_secs: i32 = 3600;
-        let offset = if timezone >= 0 {
-            FixedOffset::east_opt(hour_secs * timezone)
-        } else {
-            FixedOffset::west_opt(hour_secs * timezone.abs())
+    /// Times of `date` computed offline, with the tuning applied
+    fn compute_day(&self, date: NaiveDate) -> Option<ParsedPrayerTimeItem> {
+        let location = &self.prayer_times.location;
+        let coordinates = Coordinates {
+            latitude: location.latitude,
+            longitude: location.longitude,
+            elevation: self
+                .lookup
+                .query
+                .coordinates
+                .as_ref()
+                .map_or(0.0, |coordinates| coordinates.elevation),
         };
+        let mut item = calc::calculate_prayer_times(
+            &coordinates,
+            &self.lookup.query.settings(),
+            date,
+            &location.timezone,
+        )
+        .ok()?;
+        if let Some(tuning) = &self.lookup.tuning {
+            tuning.apply(&mut item);
+        }
 
-        let utc_now = Utc::now().naive_utc();
+        Some(item)
+    }
 
-        let timezone_now = offset.unwrap().from_utc_datetime(&utc_now).time();
+    /// Fetches the times of `date` in the background, the interface going
+    /// on with the ones computed offline until they arrive
+    fn spawn_refresh(&mut self, date: NaiveDate) {
+        let lookup = self.day_lookup();
+        let (sender, receiver) = oneshot::channel();
+
+        tokio::spawn(async move {
+            if let Ok(Ok(response)) = tokio::time::timeout(REQUEST_TIMEOUT, lookup.fetch()).await
+                && let Some(item) = response.items.into_iter().find(|item| item.date == date)
+            {
+                let _ = sender.send(item);
+            }
+        });
+        self.refresh = Some(receiver);
+    }
 
-        timezone_now
+    /// Puts the fetched times in place of the computed ones once the
+    /// provider has answered
+    fn receive_refresh(&mut self) {
+        let Some(receiver) = &mut self.refresh else {
+            return;
+        };
+
+        match receiver.try_recv() {
+            Ok(item) => {
+                self.estimated.retain(|date| *date != item.date);
+                if let Some(day) = self
+                    .prayer_times
+                    .items
+                    .iter_mut()
+                    .find(|day| day.date == item.date)
+                {
+                    *day = item;
+                }
+                self.refresh = None;
+            }
+            Err(oneshot::error::TryRecvError::Empty) => {}
+            Err(oneshot::error::TryRecvError::Closed) => self.refresh = None,
+        }
     }
 
-    fn get_next_prayer(&self) -> Option<(Prayer, NaiveTime)> {
-        let today = &self.prayer_times.items.get(0);
+    /// Moves to the next day once midnight has passed in the location,
+    /// computing it when it is not in the data and estimating it from the
+    /// day before as a last resort, while the provider is asked for it
+    fn tick(&mut self) {
+        self.receive_refresh();
+
+        let date = self.get_timezone_now().date_naive();
+        if let Some(tomorrow) =
+            navigation::missing_day(&self.prayer_times.items, self.selection.today, date)
+        {
+            let item = self.compute_day(tomorrow).unwrap_or_else(|| {
+                self.estimated.push(tomorrow);
+                self.prayer_times
+                    .day_after(self.prayer_times.items.len() - 1)
+            });
+            self.prayer_times.items.push(item);
+            self.spawn_refresh(tomorrow);
+        }
 
-        if today.is_none() {
-            return None;
+        let today = self.find_today();
+        self.selection.set_today(today);
+
+        #[cfg(feature = "audio")]
+        self.announce();
+    }
+
+    #[cfg(feature = "audio")]
+    fn announce(&mut self) {
+        if let (Some(player), Some((prayer, time))) = (&self.player, &self.pending_prayer)
+            && self.get_timezone_now() >= *time
+        {
+            player.play(prayer);
         }
 
-        let timezone_now = self.get_timezone_now();
-        let today = today.unwrap();
+        self.pending_prayer = self.get_next_prayer();
+    }
 
-        let prayer_map = today.to_hash_map();
+    fn selected_item(&self) -> &ParsedPrayerTimeItem {
+        &self.prayer_times.items[self.selection.selected]
+    }
 
-        
-        for prayer in Prayer::all_prayers() {
-            let prayer_time = prayer_map.get(&prayer).unwrap();
+    fn handle_key(&mut self, code: KeyCode) {
+        let days = self.prayer_times.items.len();
+        if self.selection.handle_key(code, self.view, days) {
+            return;
+        }
 
-            if timezone_now < *prayer_time {
-                return Some((prayer, *prayer_time));
+        match code {
+            KeyCode::Char('q') => self.should_quit = true,
+            #[cfg(feature = "audio")]
+            KeyCode::Char('s') => {
+                if let Some(player) = &self.player {
+                    player.stop();
+                }
             }
+            KeyCode::Char('v') | KeyCode::Tab => self.view = self.view.toggled(),
+            _ => {}
         }
+    }
 
-        
-        Some((Prayer::Fajr, today.fajr))
+    fn get_timezone_now(&self) -> DateTime<Tz> {
+        self.prayer_times.now()
     }
 
-    fn get_countdown_to_next_prayer(&self) -> Option<String> {
-        let next_prayer = self.get_next_prayer();
-        if next_prayer.is_none() {
-            return None;
-        }
+    fn get_next_prayer(&self) -> Option<(Prayer, DateTime<Tz>)> {
+        let today = self.selection.today?;
 
-        let (_, next_prayer_time) = next_prayer.unwrap();
+        Some(
+            self.prayer_times
+                .next_prayer(today, &self.get_timezone_now()),
+        )
+    }
 
-        let timezone_now = self.get_timezone_now();
-        
-        let now_seconds = timezone_now.num_seconds_from_midnight() as i64;
-        let prayer_seconds = next_prayer_time.num_seconds_from_midnight() as i64;
-
-        let diff_seconds = if prayer_seconds >= now_seconds {
-            prayer_seconds - now_seconds
-        } else {
-            
-            (24 * 60 * 60) - now_seconds + prayer_seconds
-        };
+    fn get_countdown_to_next_prayer(&self) -> Option<String> {
+        let (_, next_prayer_time) = self.get_next_prayer()?;
 
-        let hours = diff_seconds / 3600;
-        let minutes = (diff_seconds % 3600) / 60;
-        let seconds = diff_seconds % 60;
+        let timezone_now = self.get_timezone_now();
 
-        if hours > 0 {
-            Some(format!("{:02}:{:02}:{:02}", hours, minutes, seconds))
-        } else {
-            Some(format!("{:02}:{:02}", minutes, seconds))
-        }
+        Some(format_countdown(next_prayer_time - timezone_now))
     }
 }
 
-pub fn entry(data: ParsedPrayerTimesResponse) -> Result<(), Box<dyn std::error::Error>> {
-    
+pub fn entry(
+    data: ParsedPrayerTimesResponse,
+    lookup: Lookup,
+    display: DisplaySettings,
+    #[cfg(feature = "audio")] player: Option<AdhanPlayer>,
+) -> Result<(), AdhanError> {
     enable_raw_mode()?;
     let mut stdout = io::stdout();
     execute!(stdout, EnterAlternateScreen, EnableMouseCapture)?;
     let backend = CrosstermBackend::new(stdout);
     let mut terminal = Terminal::new(backend)?;
 
-    
-    let app = App {
+    let mut app = App {
         prayer_times: data,
+        lookup,
+        estimated: Vec::new(),
+        refresh: None,
+        selection: Selection::new(None),
+        view: View::Day,
+        table_state: TableState::default(),
+        display,
+        #[cfg(feature = "audio")]
+        player,
+        #[cfg(feature = "audio")]
+        pending_prayer: None,
         should_quit: false,
     };
+    app.selection = Selection::new(app.find_today());
 
     let res = run_app(&mut terminal, app);
 
-    
     disable_raw_mode()?;
     execute!(
         terminal.backend_mut(),
@@ -129,11 +259,7 @@ pub fn entry(data: ParsedPrayerTimesResponse) -> Result<(), Box<dyn std::error::
     )?;
     terminal.show_cursor()?;
 
-    if let Err(err) = res {
-        println!("{err:?}");
-    }
-
-    Ok(())
+    Ok(res?)
 }
 
 pub fn run_app<B: ratatui::backend::Backend>(
@@ -141,17 +267,13 @@ pub fn run_app<B: ratatui::backend::Backend>(
     mut app: App,
 ) -> io::Result<()> {
     loop {
+        app.tick();
         ui(terminal, &mut app)?;
 
-        if event::poll(std::time::Duration::from_millis(1000))? {
-            if let Event::Key(key) = event::read()? {
-                match key.code {
-                    KeyCode::Char('q') => {
-                        app.should_quit = true;
-                    }
-                    _ => {}
-                }
-            }
+        if event::poll(std::time::Duration::from_millis(1000))?
+            && let Event::Key(key) = event::read()?
+        {
+            app.handle_key(key.code);
         }
 
         if app.should_quit {
@@ -183,6 +305,11 @@ pub fn draw_main(frame: &mut Frame, app: &mut App) {
 
     let menus_rect = main_layout[1];
 
+    if app.view == View::Table {
+        draw_timetable(frame, &menus_rect, app);
+        return;
+    }
+
     let menus_layout = Layout::default()
         .direction(Direction::Horizontal)
         .margin(1)
@@ -199,29 +326,75 @@ pub fn draw_main(frame: &mut Frame, app: &mut App) {
 }
 
 pub fn draw_header(frame: &mut Frame, rect: &Rect, app: &mut App) {
+    let theme = app.display.theme;
+    let time_format = app.display.time_format.pattern();
     let block = Block::default()
         .title("Current Date")
         .title_alignment(Alignment::Center)
         .borders(Borders::ALL)
-        .border_style(Style::new().cyan()); 
+        .border_style(Style::new().fg(theme.border));
 
-    let full_date = app.prayer_times.items[0].date.format("%D").to_string();
-    let hour = app.get_timezone_now().format("%-I:%M %p").to_string();
+    let selected = app.selected_item();
+    let mut full_date = selected.date.format("%a %D").to_string();
+    if let Some(hijri) = &selected.hijri {
+        full_date = format!("{full_date} ({hijri})");
+    }
+    let hour = app.get_timezone_now().format(time_format).to_string();
     let location = format!(
         "{} {}",
         app.prayer_times.location.country, app.prayer_times.location.city
     );
-    let text = format!("{location} {full_date} {hour}");
+    let mut method = app.prayer_times.method_name.clone();
+    if let Some(rule) = app.prayer_times.high_latitude_rule {
+        method = format!("{method}, {}", rule.name());
+    }
+    let provider = app.lookup.provider.name();
+    let days = app.prayer_times.items.len();
+    let mut text = format!("{location} {full_date} {hour} | {method} | {provider}");
+    if days > 1 {
+        text = format!("{text} | day {}/{days}", app.selection.selected + 1);
+    }
+
+    let mut line = Line::from(text);
+    for warning in navigation::warnings(&app.selection, &app.estimated, selected.date) {
+        line.push_span(Span::styled(
+            format!(" [{warning}]"),
+            Style::new().black().on_red().add_modifier(Modifier::BOLD),
+        ));
+    }
+    match app.prayer_times.source {
+        DataSource::Live => {}
+        DataSource::Cached(fetched_at) => line.push_span(Span::styled(
+            format!(
+                " [cached {}]",
+                fetched_at
+                    .with_timezone(&Local)
+                    .format(&format!("%D {time_format}"))
+            ),
+            Style::new().fg(theme.muted),
+        )),
+        DataSource::Stale(fetched_at) => line.push_span(Span::styled(
+            format!(
+                " [offline / cached {}]",
+                fetched_at
+                    .with_timezone(&Local)
+                    .format(&format!("%D {time_format}"))
+            ),
+            Style::new().black().on_red().add_modifier(Modifier::BOLD),
+        )),
+    }
 
-    let widget = Paragraph::new(text)
+    let widget = Paragraph::new(line)
         .alignment(Alignment::Center)
         .block(block)
-        .style(Style::new().yellow()); 
+        .style(Style::new().fg(theme.text));
 
     frame.render_widget(widget, *rect);
 }
 
 pub fn draw_prayers_list(frame: &mut Frame, rect: &Rect, app: &mut App) {
+    let theme = app.display.theme;
+    let time_format = app.display.time_format.pattern();
     let prayer_list_layout = Layout::default()
         .direction(Direction::Vertical)
         .constraints(vec![Constraint::Percentage(85), Constraint::Percentage(15)])
@@ -230,24 +403,25 @@ pub fn draw_prayers_list(frame: &mut Frame, rect: &Rect, app: &mut App) {
     let prayer_items: Vec<ListItem> = Prayer::all_prayers()
         .iter()
         .flat_map(|prayer| {
-            let mut style = Style::new().fg(Color::LightCyan).italic();
-
-            let (next_prayer, _) = app.get_next_prayer().unwrap();
+            let mut style = Style::new().fg(theme.item).italic();
 
-            if next_prayer == *prayer {
+            if app.selection.is_today_selected()
+                && let Some((next_prayer, _)) = app.get_next_prayer()
+                && next_prayer == *prayer
+            {
                 style = style
-                    .bg(Color::Magenta)
-                    .fg(Color::White)
+                    .bg(theme.highlight_bg)
+                    .fg(theme.highlight_fg)
                     .add_modifier(Modifier::BOLD);
             }
 
-            let prayer_time = app.prayer_times.items[0].get_prayer(prayer);
+            let prayer_time = app.selected_item().get_prayer(prayer);
+            let mut content = format!("{} {}", prayer.name(), prayer_time.format(time_format));
+            if *prayer == Prayer::Asr {
+                content = format!("{content} ({})", app.prayer_times.asr_school.name());
+            }
             let text = Span::default()
-                .content(format!(
-                    "{} {}",
-                    prayer.name(),
-                    prayer_time.format("%-I:%M %p").to_string()
-                ))
+                .content(content)
                 .style(Style::new().underlined())
                 .into_centered_line();
 
@@ -263,7 +437,7 @@ pub fn draw_prayers_list(frame: &mut Frame, rect: &Rect, app: &mut App) {
         .title("Prayers Time")
         .title_alignment(Alignment::Center)
         .borders(Borders::ALL)
-        .border_style(Style::new().cyan());
+        .border_style(Style::new().fg(theme.border));
     let list: List = List::default()
         .items(prayer_items)
         .block(block)
@@ -274,28 +448,110 @@ pub fn draw_prayers_list(frame: &mut Frame, rect: &Rect, app: &mut App) {
         .title("Next Prayer")
         .title_alignment(Alignment::Center)
         .borders(Borders::ALL)
-        .border_style(Style::new().cyan());
+        .border_style(Style::new().fg(theme.border));
+
+    let next_prayer_span = match (app.get_next_prayer(), app.get_countdown_to_next_prayer()) {
+        (Some((next_prayer, next_prayer_time)), Some(prayer_count_down)) => {
+            let next_prayer_text = format!(
+                "{} {} ({})",
+                next_prayer.name(),
+                next_prayer_time.format(time_format),
+                prayer_count_down
+            );
+
+            Paragraph::new(next_prayer_text)
+                .style(Style::new().fg(theme.text).add_modifier(Modifier::BOLD))
+        }
+        _ => Paragraph::new("No prayer times for today")
+            .style(Style::new().black().on_red().add_modifier(Modifier::BOLD)),
+    }
+    .alignment(Alignment::Center)
+    .block(next_prayer_block);
 
-    let (next_prayer, next_prayer_time) = app.get_next_prayer().unwrap();
-    let prayer_count_down = app.get_countdown_to_next_prayer().unwrap();
+    frame.render_widget(list, prayer_list_layout[0]);
+    frame.render_widget(next_prayer_span, prayer_list_layout[1]);
+}
 
-    let next_prayer_text = format!(
-        "{} {} ({})",
-        next_prayer.name(),
-        next_prayer_time.format("%-I:%M %p").to_string(),
-        prayer_count_down
-    );
+pub fn draw_timetable(frame: &mut Frame, rect: &Rect, app: &mut App) {
+    let theme = app.display.theme;
+    let time_format = app.display.time_format.pattern();
+    let prayers = Prayer::all_prayers();
+
+    let header = ["Date", "Day", "Hijri"]
+        .into_iter()
+        .chain(prayers.iter().map(|prayer| prayer.name()))
+        .map(Cell::from)
+        .collect::<Row>()
+        .style(Style::new().fg(theme.text).add_modifier(Modifier::BOLD))
+        .bottom_margin(1);
+
+    let today = app.selection.today;
+    let rows: Vec<Row> = app
+        .prayer_times
+        .items
+        .iter()
+        .enumerate()
+        .map(|(index, item)| {
+            let hijri = match &item.hijri {
+                Some(hijri) => format!("{} {}", hijri.day, hijri.month_name),
+                None => "-".to_string(),
+            };
+            let cells = [
+                item.date.format("%D").to_string(),
+                item.date.format("%a").to_string(),
+                hijri,
+            ]
+            .into_iter()
+            .chain(
+                prayers
+                    .iter()
+                    .map(|prayer| item.get_prayer(prayer).format(time_format).to_string()),
+            );
+
+            let mut style = Style::new().fg(theme.item);
+            if item.date.weekday() == Weekday::Fri {
+                style = style.fg(theme.friday);
+            }
+            if Some(index) == today {
+                style = style.bg(theme.highlight_bg).fg(theme.highlight_fg);
+            }
 
-    let next_prayer_span = Paragraph::new(next_prayer_text)
-        .alignment(Alignment::Center)
-        .block(next_prayer_block)
-        .style(Style::new().yellow().add_modifier(Modifier::BOLD));
+            Row::new(cells).style(style)
+        })
+        .collect();
 
-    frame.render_widget(list, prayer_list_layout[0]);
-    frame.render_widget(next_prayer_span, prayer_list_layout[1]);
+    let mut widths = vec![
+        Constraint::Length(10),
+        Constraint::Length(5),
+        Constraint::Min(14),
+    ];
+    widths.extend(prayers.iter().map(|_| Constraint::Length(9)));
+
+    let block = Block::default()
+        .title("Timetable")
+        .title_alignment(Alignment::Center)
+        .borders(Borders::ALL)
+        .border_style(Style::new().fg(theme.border));
+
+    let table = Table::new(rows, widths)
+        .header(header)
+        .block(block)
+        .row_highlight_style(Style::new().add_modifier(Modifier::REVERSED | Modifier::BOLD))
+        .highlight_spacing(HighlightSpacing::Always)
+        .highlight_symbol("> ");
+
+    app.table_state.select(Some(app.selection.selected));
+    frame.render_stateful_widget(table, *rect, &mut app.table_state);
 }
 
+/// Degrees by which the Qibla given by the provider may differ from the one
+/// computed locally before both are shown
+const QIBLA_TOLERANCE: f64 = 1.0;
+
 fn draw_qibla(frame: &mut Frame, rect: &Rect, app: &mut App) {
+    let theme = app.display.theme;
+    let location = &app.prayer_times.location;
+    let qibla = location.qibla();
     let c_layout = Layout::default()
         .direction(Direction::Horizontal)
         .constraints(vec![
@@ -309,8 +565,9 @@ fn draw_qibla(frame: &mut Frame, rect: &Rect, app: &mut App) {
         .direction(Direction::Vertical)
         .constraints(vec![
             Constraint::Percentage(10),
-            Constraint::Percentage(80),
-            Constraint::Percentage(10),
+            Constraint::Min(0),
+            Constraint::Length(2),
+            Constraint::Length(1),
         ])
         .split(c_layout[1]);
 
@@ -318,10 +575,10 @@ fn draw_qibla(frame: &mut Frame, rect: &Rect, app: &mut App) {
         .title("Qibla")
         .title_alignment(Alignment::Center)
         .borders(Borders::ALL)
-        .border_style(Style::new().cyan())
+        .border_style(Style::new().fg(theme.border))
         .style(Style::new());
 
-    let qibla_angle = 90.0f64.to_radians() - app.prayer_times.location.qibla_direction.to_radians();
+    let qibla_angle = 90.0f64.to_radians() - qibla.bearing.to_radians();
 
     let x_bounds = [0.0, 100.0];
     let y_bounds = [0.0, 100.0];
@@ -332,14 +589,14 @@ fn draw_qibla(frame: &mut Frame, rect: &Rect, app: &mut App) {
         .y_bounds(y_bounds)
         .paint(|ctx| {
             let circle = Circle {
-                color: Color::Magenta,
+                color: theme.highlight_bg,
                 x: 50.0,
                 y: 50.0,
                 radius: 30.0,
             };
 
             let line = CanvasLine {
-                color: Color::Yellow,
+                color: theme.text,
                 x1: circle.x,
                 y1: circle.y,
                 x2: circle.x + 3.0 * circle.radius / 4.0 * qibla_angle.cos(),
@@ -351,6 +608,39 @@ fn draw_qibla(frame: &mut Frame, rect: &Rect, app: &mut App) {
             ctx.draw(&line);
         });
 
+    let mut lines = Vec::new();
+    // The bearing means nothing once at the Masjid al-Haram
+    if qibla.distance < 1.0 {
+        lines.push(Line::from("At the Kaaba").fg(theme.text).bold());
+    } else {
+        lines.push(Line::from(vec![
+            Span::styled(
+                format!("{:.1}° {}", qibla.bearing, qibla.cardinal()),
+                Style::default().fg(theme.text).bold(),
+            ),
+            Span::styled(
+                format!(" · {:.0} km to the Kaaba", qibla.distance),
+                Style::default().fg(theme.item),
+            ),
+        ]));
+
+        let deviation = qibla.deviation(location.qibla_direction);
+        if deviation > QIBLA_TOLERANCE {
+            lines.push(Line::from(Span::styled(
+                format!(
+                    "{} gives {:.1}°, {deviation:.1}° apart",
+                    app.lookup.provider.name(),
+                    location.qibla_direction
+                ),
+                Style::default().fg(theme.muted),
+            )));
+        }
+    }
+
     frame.render_widget(block, *rect);
     frame.render_widget(canvas, d_layout[1]);
+    frame.render_widget(
+        Paragraph::new(lines).alignment(Alignment::Center),
+        d_layout[2],
+    );
 }
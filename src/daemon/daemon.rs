@@ -0,0 +1,91 @@
+use std::time::Duration;
+
+use chrono::{DateTime, TimeDelta, Utc};
+use chrono_tz::Tz;
+
+use crate::{
+    api::{AdhanError, Lookup},
+    daemon::{HookRunner, Notifier, ScheduleSettings, next_midnight, upcoming_events},
+};
+
+/// Longest single sleep, so that the wall clock is checked again after the
+/// machine wakes up from suspend
+const MAX_SLEEP: Duration = Duration::from_secs(60);
+
+/// Events found this late, e.g. after a suspend, are dropped
+const MAX_LATENESS: TimeDelta = TimeDelta::minutes(5);
+
+/// Delay before fetching again when the prayer times could not be fetched
+/// or do not cover the current day
+const RETRY_DELAY: Duration = Duration::from_secs(5 * 60);
+
+async fn sleep_until(at: &DateTime<Tz>) {
+    loop {
+        let Ok(remaining) = at.signed_duration_since(Utc::now()).to_std() else {
+            return;
+        };
+        if remaining.is_zero() {
+            return;
+        }
+
+        tokio::time::sleep(remaining.min(MAX_SLEEP)).await;
+    }
+}
+
+/// Passes every prayer event to the notifiers and runs its hooks until
+/// stopped, fetching the prayer times again once each day is over
+pub async fn run(
+    lookup: &Lookup,
+    settings: &ScheduleSettings,
+    notifiers: &[Box<dyn Notifier>],
+    hooks: &HookRunner,
+) -> Result<(), AdhanError> {
+    loop {
+        let response = match lookup.fetch().await {
+            Ok(response) => response,
+            Err(error) => {
+                eprintln!("Could not fetch the prayer times: {error}");
+                tokio::time::sleep(RETRY_DELAY).await;
+                continue;
+            }
+        };
+
+        let place = response.location.place();
+        let now = response.now();
+        let today = now.date_naive();
+
+        let events = match upcoming_events(&response, settings, &now) {
+            Ok(events) => events,
+            Err(error) => {
+                eprintln!("Could not schedule the prayer times: {error}");
+                tokio::time::sleep(RETRY_DELAY).await;
+                continue;
+            }
+        };
+
+        // Today's last events, such as an Isha after midnight, may come
+        // after the next midnight
+        for event in events {
+            if event.day > today {
+                break;
+            }
+
+            sleep_until(&event.at).await;
+            if Utc::now().signed_duration_since(event.at) > MAX_LATENESS {
+                continue;
+            }
+
+            hooks.run(&event, &response.location);
+            for notifier in notifiers {
+                if let Err(error) = notifier.notify(&event, &place).await {
+                    eprintln!(
+                        "Could not send the {} notification: {error}",
+                        event.prayer.name()
+                    );
+                }
+            }
+        }
+
+        sleep_until(&next_midnight(&now)).await;
+    }
+}
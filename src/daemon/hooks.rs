@@ -0,0 +1,198 @@
+use std::{process::Stdio, str::FromStr, time::Duration};
+
+use tokio::process::Command;
+
+use crate::{
+    api::{AdhanError, ParsedLocation, Prayer},
+    daemon::{EventKind, ScheduleSettings, ScheduledEvent},
+};
+
+/// Shell command run on an event, for every prayer or a single one
+#[derive(Debug, Clone, PartialEq)]
+pub struct Hook {
+    pub kind: EventKind,
+    pub prayer: Option<Prayer>,
+    pub command: String,
+}
+
+impl Hook {
+    pub fn matches(&self, event: &ScheduledEvent) -> bool {
+        self.kind == event.kind
+            && self
+                .prayer
+                .as_ref()
+                .is_none_or(|prayer| *prayer == event.prayer)
+    }
+}
+
+impl FromStr for Hook {
+    type Err = AdhanError;
+
+    /// Parses `<event>[:<prayer>]=<command>` such as `at-time=playerctl pause`
+    /// or `iqamah:maghrib=notify-send Iqamah`
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let (target, command) = s.split_once('=').ok_or(AdhanError::InvalidHook)?;
+        let command = command.trim();
+        if command.is_empty() {
+            return Err(AdhanError::InvalidHook);
+        }
+
+        let (kind, prayer) = match target.split_once(':') {
+            Some((kind, prayer)) => (kind, Some(Prayer::from_str(prayer.trim())?)),
+            None => (target, None),
+        };
+
+        Ok(Hook {
+            kind: EventKind::from_str(kind.trim())?,
+            prayer,
+            command: command.to_string(),
+        })
+    }
+}
+
+/// Runs the hooks matching each event with `sh -c`, the event being
+/// described by `ADHAN_*` environment variables
+pub struct HookRunner {
+    hooks: Vec<Hook>,
+    timeout: Duration,
+}
+
+impl HookRunner {
+    pub fn new(hooks: Vec<Hook>, timeout: Duration) -> Self {
+        HookRunner { hooks, timeout }
+    }
+
+    pub fn uses(&self, kind: EventKind) -> bool {
+        self.hooks.iter().any(|hook| hook.kind == kind)
+    }
+
+    /// Fails on a hook whose event `settings` never schedule, such as a
+    /// pre-alert hook without a pre-alert
+    pub fn check(&self, settings: &ScheduleSettings) -> Result<(), AdhanError> {
+        for hook in &self.hooks {
+            let target = match &hook.prayer {
+                Some(prayer) => format!("{}:{}", hook.kind, prayer.name().to_lowercase()),
+                None => hook.kind.to_string(),
+            };
+
+            let requirement = match (hook.kind, &hook.prayer) {
+                (EventKind::PreAlert, _)
+                    if settings.pre_alert.is_none_or(|minutes| minutes == 0) =>
+                {
+                    "--pre-alert".to_string()
+                }
+                (EventKind::Iqamah, None)
+                    if Prayer::all_prayers()
+                        .iter()
+                        .all(|prayer| settings.iqamah.offset(prayer) <= 0) =>
+                {
+                    "--iqamah".to_string()
+                }
+                (EventKind::Iqamah, Some(prayer)) if settings.iqamah.offset(prayer) <= 0 => {
+                    format!("an iqamah for {} in --iqamah", prayer.name())
+                }
+                _ => continue,
+            };
+
+            return Err(AdhanError::HookNeverRuns(target, requirement));
+        }
+
+        Ok(())
+    }
+
+    /// Starts the matching hooks in the background, logging how each ends
+    pub fn run(&self, event: &ScheduledEvent, location: &ParsedLocation) {
+        for hook in self.hooks.iter().filter(|hook| hook.matches(event)) {
+            let mut command = Command::new("sh");
+            command
+                .arg("-c")
+                .arg(&hook.command)
+                .env("ADHAN_PRAYER", event.prayer.name())
+                .env("ADHAN_EVENT", event.kind.to_string())
+                .env("ADHAN_TIME", event.prayer_time.format("%H:%M").to_string())
+                .env("ADHAN_DATETIME", event.prayer_time.to_rfc3339())
+                .env("ADHAN_CITY", &location.city)
+                .env("ADHAN_COUNTRY", &location.country)
+                .env("ADHAN_TIMEZONE", location.timezone.name())
+                .stdin(Stdio::null())
+                .kill_on_drop(true);
+
+            let name = format!(
+                "{} {} hook `{}`",
+                event.prayer.name(),
+                event.kind,
+                hook.command
+            );
+            let timeout = self.timeout;
+
+            tokio::spawn(async move {
+                let mut child = match command.spawn() {
+                    Ok(child) => child,
+                    Err(error) => {
+                        eprintln!("{name} could not start: {error}");
+                        return;
+                    }
+                };
+
+                match tokio::time::timeout(timeout, child.wait()).await {
+                    Ok(Ok(status)) => eprintln!("{name} exited with {status}"),
+                    Ok(Err(error)) => eprintln!("{name} failed: {error}"),
+                    Err(_) => {
+                        let _ = child.kill().await;
+                        eprintln!("{name} killed after {}s", timeout.as_secs());
+                    }
+                }
+            });
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn runner(hooks: &[&str]) -> HookRunner {
+        let hooks = hooks.iter().map(|hook| hook.parse().unwrap()).collect();
+        HookRunner::new(hooks, Duration::from_secs(30))
+    }
+
+    #[test]
+    fn parses_the_event_prayer_and_command() {
+        let hook: Hook = "iqamah:maghrib = notify-send Iqamah".parse().unwrap();
+
+        assert_eq!(hook.kind, EventKind::Iqamah);
+        assert_eq!(hook.prayer, Some(Prayer::Maghrib));
+        assert_eq!(hook.command, "notify-send Iqamah");
+        assert!("at-time=".parse::<Hook>().is_err());
+    }
+
+    #[test]
+    fn rejects_hooks_of_events_never_scheduled() {
+        let settings = ScheduleSettings::default();
+        assert!(
+            runner(&["at-time=true", "end-of-window=true"])
+                .check(&settings)
+                .is_ok()
+        );
+        assert!(matches!(
+            runner(&["pre-alert=true"]).check(&settings),
+            Err(AdhanError::HookNeverRuns(target, _)) if target == "pre-alert"
+        ));
+        assert!(runner(&["iqamah=true"]).check(&settings).is_err());
+
+        let mut settings = ScheduleSettings {
+            pre_alert: Some(10),
+            ..ScheduleSettings::default()
+        };
+        settings.iqamah.set_offset(Prayer::Fajr, 20);
+        assert!(
+            runner(&["pre-alert=true", "iqamah=true", "iqamah:fajr=true"])
+                .check(&settings)
+                .is_ok()
+        );
+        assert!(matches!(
+            runner(&["iqamah:isha=true"]).check(&settings),
+            Err(AdhanError::HookNeverRuns(target, _)) if target == "iqamah:isha"
+        ));
+    }
+}
@@ -0,0 +1,10 @@
+#[allow(clippy::module_inception)]
+mod daemon;
+mod hooks;
+mod notifier;
+mod schedule;
+
+pub use daemon::*;
+pub use hooks::*;
+pub use notifier::*;
+pub use schedule::*;
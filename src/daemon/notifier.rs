@@ -0,0 +1,263 @@
+use std::collections::HashMap;
+
+use async_trait::async_trait;
+use zbus::{Connection, proxy, zvariant::Value};
+
+use crate::{
+    api::AdhanError,
+    daemon::{EventKind, ScheduledEvent},
+};
+
+#[proxy(
+    interface = "org.freedesktop.Notifications",
+    default_service = "org.freedesktop.Notifications",
+    default_path = "/org/freedesktop/Notifications"
+)]
+trait Notifications {
+    #[allow(clippy::too_many_arguments)]
+    fn notify(
+        &self,
+        app_name: &str,
+        replaces_id: u32,
+        app_icon: &str,
+        summary: &str,
+        body: &str,
+        actions: &[&str],
+        hints: HashMap<&str, Value<'_>>,
+        expire_timeout: i32,
+    ) -> zbus::Result<u32>;
+}
+
+/// Tells the user about a prayer event
+#[async_trait]
+pub trait Notifier: Send + Sync {
+    async fn notify(&self, event: &ScheduledEvent, place: &str) -> Result<(), AdhanError>;
+}
+
+/// Title and body of the notification of `event`
+pub fn notification_text(event: &ScheduledEvent, place: &str) -> (String, String) {
+    let time = event.prayer_time.format("%-I:%M %p");
+
+    match event.kind {
+        EventKind::PreAlert => {
+            let minutes = (event.prayer_time - event.at).num_minutes();
+            (
+                format!("{} in {minutes} minutes", event.prayer.name()),
+                format!("{} at {time} in {place}", event.prayer.name()),
+            )
+        }
+        EventKind::AtTime => (
+            format!("{} time", event.prayer.name()),
+            format!("{} at {time} in {place}", event.prayer.name()),
+        ),
+        EventKind::Iqamah => (
+            format!("{} iqamah", event.prayer.name()),
+            format!("{} was at {time} in {place}", event.prayer.name()),
+        ),
+        EventKind::EndOfWindow => (
+            format!("{} time is over", event.prayer.name()),
+            format!("{} was at {time} in {place}", event.prayer.name()),
+        ),
+    }
+}
+
+/// Desktop notifications through the freedesktop `org.freedesktop.Notifications`
+/// service
+pub struct DbusNotifier {
+    proxy: NotificationsProxy<'static>,
+}
+
+impl DbusNotifier {
+    /// Connects to the bus at `address`, the session bus when `None`
+    pub async fn connect(address: Option<&str>) -> Result<Self, AdhanError> {
+        let connection = match address {
+            Some(address) => zbus::connection::Builder::address(address)?.build().await?,
+            None => Connection::session().await?,
+        };
+
+        Self::new(&connection).await
+    }
+
+    /// Sends the notifications over `connection`
+    pub async fn new(connection: &Connection) -> Result<Self, AdhanError> {
+        let proxy = NotificationsProxy::new(connection).await?;
+
+        Ok(DbusNotifier { proxy })
+    }
+}
+
+#[async_trait]
+impl Notifier for DbusNotifier {
+    async fn notify(&self, event: &ScheduledEvent, place: &str) -> Result<(), AdhanError> {
+        let (summary, body) = notification_text(event, place);
+        let mut hints = HashMap::new();
+        hints.insert("category", Value::from("x-adhan.prayer"));
+
+        self.proxy
+            .notify("Adhan", 0, "", &summary, &body, &[], hints, -1)
+            .await?;
+
+        Ok(())
+    }
+}
+
+/// Prints the notifications instead of showing them, for terminals and
+/// machines without a notification server
+pub struct StdoutNotifier;
+
+#[async_trait]
+impl Notifier for StdoutNotifier {
+    async fn notify(&self, event: &ScheduledEvent, place: &str) -> Result<(), AdhanError> {
+        let (summary, body) = notification_text(event, place);
+        println!("{summary}: {body}");
+
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::sync::{Arc, Mutex};
+
+    use chrono::{NaiveDate, TimeDelta, TimeZone};
+    use chrono_tz::Africa::Algiers;
+    use zbus::{connection, zvariant::OwnedValue};
+
+    use super::*;
+    use crate::api::Prayer;
+
+    /// Arguments of a `Notify` call
+    #[derive(Debug, PartialEq)]
+    struct Notification {
+        app_name: String,
+        replaces_id: u32,
+        app_icon: String,
+        summary: String,
+        body: String,
+        actions: Vec<String>,
+        category: Option<String>,
+        expire_timeout: i32,
+    }
+
+    /// Notification server recording the notifications it is sent
+    struct FakeNotifications {
+        received: Arc<Mutex<Vec<Notification>>>,
+    }
+
+    #[zbus::interface(name = "org.freedesktop.Notifications")]
+    impl FakeNotifications {
+        #[allow(clippy::too_many_arguments)]
+        fn notify(
+            &self,
+            app_name: String,
+            replaces_id: u32,
+            app_icon: String,
+            summary: String,
+            body: String,
+            actions: Vec<String>,
+            hints: HashMap<String, OwnedValue>,
+            expire_timeout: i32,
+        ) -> u32 {
+            let category = hints
+                .get("category")
+                .and_then(|category| String::try_from(category.clone()).ok());
+            let mut received = self.received.lock().unwrap();
+            received.push(Notification {
+                app_name,
+                replaces_id,
+                app_icon,
+                summary,
+                body,
+                actions,
+                category,
+                expire_timeout,
+            });
+
+            received.len() as u32
+        }
+    }
+
+    fn event(kind: EventKind, minutes_after_prayer: i64) -> ScheduledEvent {
+        let prayer_time = Algiers.with_ymd_and_hms(2026, 10, 17, 12, 44, 0).unwrap();
+
+        ScheduledEvent {
+            prayer: Prayer::Dhuhr,
+            kind,
+            at: prayer_time + TimeDelta::minutes(minutes_after_prayer),
+            prayer_time,
+            day: NaiveDate::from_ymd_opt(2026, 10, 17).unwrap(),
+        }
+    }
+
+    fn text(title: &str, body: &str) -> (String, String) {
+        (title.to_string(), body.to_string())
+    }
+
+    #[test]
+    fn tells_each_kind_of_event_apart() {
+        let place = "Algiers, Algeria";
+
+        assert_eq!(
+            notification_text(&event(EventKind::PreAlert, -15), place),
+            text(
+                "Dhuhr in 15 minutes",
+                "Dhuhr at 12:44 PM in Algiers, Algeria"
+            )
+        );
+        assert_eq!(
+            notification_text(&event(EventKind::AtTime, 0), place),
+            text("Dhuhr time", "Dhuhr at 12:44 PM in Algiers, Algeria")
+        );
+        assert_eq!(
+            notification_text(&event(EventKind::Iqamah, 10), place),
+            text("Dhuhr iqamah", "Dhuhr was at 12:44 PM in Algiers, Algeria")
+        );
+        assert_eq!(
+            notification_text(&event(EventKind::EndOfWindow, 191), place),
+            text(
+                "Dhuhr time is over",
+                "Dhuhr was at 12:44 PM in Algiers, Algeria"
+            )
+        );
+    }
+
+    #[tokio::test]
+    async fn sends_notify_calls() {
+        let received = Arc::new(Mutex::new(Vec::new()));
+        let (server, client) = tokio::net::UnixStream::pair().unwrap();
+        let server = connection::Builder::unix_stream(server)
+            .server(zbus::Guid::generate())
+            .unwrap()
+            .p2p()
+            .serve_at(
+                "/org/freedesktop/Notifications",
+                FakeNotifications {
+                    received: received.clone(),
+                },
+            )
+            .unwrap()
+            .build();
+        let client = connection::Builder::unix_stream(client).p2p().build();
+        let (_server, client) = tokio::try_join!(server, client).unwrap();
+
+        let notifier = DbusNotifier::new(&client).await.unwrap();
+        notifier
+            .notify(&event(EventKind::PreAlert, -15), "Algiers, Algeria")
+            .await
+            .unwrap();
+
+        assert_eq!(
+            *received.lock().unwrap(),
+            [Notification {
+                app_name: "Adhan".to_string(),
+                replaces_id: 0,
+                app_icon: String::new(),
+                summary: "Dhuhr in 15 minutes".to_string(),
+                body: "Dhuhr at 12:44 PM in Algiers, Algeria".to_string(),
+                actions: Vec::new(),
+                category: Some("x-adhan.prayer".to_string()),
+                expire_timeout: -1,
+            }]
+        );
+    }
+}
@@ -0,0 +1,306 @@
+use std::str::FromStr;
+
+use chrono::{DateTime, NaiveDate, TimeDelta};
+use chrono_tz::Tz;
+
+use crate::api::{
+    AdhanError, ParsedPrayerTimeItem, ParsedPrayerTimesResponse, Prayer, Tuning, localize,
+};
+
+/// Moment of a prayer the daemon reacts to
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum EventKind {
+    /// Some minutes before the prayer
+    PreAlert,
+    /// When the prayer time begins
+    AtTime,
+    /// When the congregation stands for the prayer, some minutes after it
+    Iqamah,
+    /// When the time of the prayer ends, at the start of the next one
+    EndOfWindow,
+}
+
+impl EventKind {
+    pub fn all_kinds() -> [EventKind; 4] {
+        [
+            EventKind::PreAlert,
+            EventKind::AtTime,
+            EventKind::Iqamah,
+            EventKind::EndOfWindow,
+        ]
+    }
+}
+
+impl std::fmt::Display for EventKind {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let data = match self {
+            Self::PreAlert => "pre-alert",
+            Self::AtTime => "at-time",
+            Self::Iqamah => "iqamah",
+            Self::EndOfWindow => "end-of-window",
+        };
+
+        write!(f, "{}", data)
+    }
+}
+
+impl FromStr for EventKind {
+    type Err = AdhanError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        Self::all_kinds()
+            .into_iter()
+            .find(|kind| kind.to_string() == s.to_lowercase())
+            .ok_or(AdhanError::InvalidEventKind)
+    }
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct ScheduledEvent {
+    pub prayer: Prayer,
+    pub kind: EventKind,
+    /// When the event fires
+    pub at: DateTime<Tz>,
+    /// Time of the prayer itself
+    pub prayer_time: DateTime<Tz>,
+    /// Day the prayer belongs to, the day before the event for an Isha
+    /// after midnight
+    pub day: NaiveDate,
+}
+
+/// When the daemon reacts to prayers
+#[derive(Debug, Clone, Default)]
+pub struct ScheduleSettings {
+    /// Minutes before each prayer for a pre-alert, none when `None`
+    pub pre_alert: Option<u32>,
+    /// Minutes between each prayer and its iqamah, prayers left out have
+    /// no iqamah event
+    pub iqamah: Tuning,
+    /// Whether to fire an event when the time of each prayer ends
+    pub end_of_window: bool,
+}
+
+/// End of the time of `prayer`, the start of the next prayer of `day`, or
+/// Fajr of `next_day` for Isha. Shurooq has no window of its own
+fn window_end(
+    prayer: &Prayer,
+    day: &ParsedPrayerTimeItem,
+    next_day: &ParsedPrayerTimeItem,
+) -> Option<DateTime<Tz>> {
+    match prayer {
+        Prayer::Fajr => Some(day.shurooq),
+        Prayer::Shurooq => None,
+        Prayer::Dhuhr => Some(day.asr),
+        Prayer::Asr => Some(day.maghrib),
+        Prayer::Maghrib => Some(day.isha),
+        Prayer::Isha => Some(next_day.fajr),
+    }
+}
+
+/// Events of yesterday, today and tomorrow still to come after `now`, in
+/// order. Those of yesterday are the ones falling after midnight.
+pub fn upcoming_events(
+    response: &ParsedPrayerTimesResponse,
+    settings: &ScheduleSettings,
+    now: &DateTime<Tz>,
+) -> Result<Vec<ScheduledEvent>, AdhanError> {
+    let today = response
+        .today_index(now)
+        .ok_or(AdhanError::TodayNotCovered)?;
+    let yesterday = response.day_before(today);
+    let tomorrow = response.day_after(today);
+    let day_after_tomorrow = match response.items.get(today + 2) {
+        Some(item) => item.clone(),
+        None => tomorrow.next_day_estimate(),
+    };
+    let days = [
+        (&yesterday, &response.items[today]),
+        (&response.items[today], &tomorrow),
+        (&tomorrow, &day_after_tomorrow),
+    ];
+
+    let mut events = Vec::new();
+    for (day, next_day) in days {
+        for prayer in Prayer::all_prayers() {
+            let prayer_time = day.get_prayer(&prayer);
+            let mut push = |kind, at| {
+                events.push(ScheduledEvent {
+                    prayer: prayer.clone(),
+                    kind,
+                    at,
+                    prayer_time,
+                    day: day.date,
+                })
+            };
+
+            if let Some(minutes) = settings.pre_alert.filter(|minutes| *minutes > 0) {
+                push(
+                    EventKind::PreAlert,
+                    prayer_time - TimeDelta::minutes(minutes as i64),
+                );
+            }
+
+            push(EventKind::AtTime, prayer_time);
+
+            let iqamah = settings.iqamah.offset(&prayer);
+            if iqamah > 0 {
+                push(EventKind::Iqamah, prayer_time + TimeDelta::minutes(iqamah));
+            }
+
+            if settings.end_of_window
+                && let Some(end) = window_end(&prayer, day, next_day)
+            {
+                push(EventKind::EndOfWindow, end);
+            }
+        }
+    }
+
+    events.retain(|event| event.at > *now);
+    events.sort_by_key(|event| event.at);
+
+    Ok(events)
+}
+
+/// Start of the day after `now` in its zone, when the schedule is refreshed
+pub fn next_midnight(now: &DateTime<Tz>) -> DateTime<Tz> {
+    let tomorrow = now.date_naive() + TimeDelta::days(1);
+    localize(&now.timezone(), tomorrow.and_hms_opt(0, 0, 0).unwrap())
+}
+
+#[cfg(test)]
+mod tests {
+    use chrono::{NaiveTime, TimeZone};
+    use chrono_tz::Europe::Oslo;
+
+    use super::*;
+    use crate::{
+        api::{DataSource, ParsedLocation, PrayerTimesPeriod},
+        calc::AsrSchool,
+    };
+
+    /// Two days at midsummer in Oslo, where Isha falls after midnight
+    fn oslo() -> ParsedPrayerTimesResponse {
+        let time = |text: &str| NaiveTime::parse_from_str(text, "%H:%M").unwrap();
+        let first = NaiveDate::from_ymd_opt(2026, 6, 20).unwrap();
+        let items = (0..2)
+            .map(|days| {
+                ParsedPrayerTimeItem::from_local_times(
+                    &Oslo,
+                    first + TimeDelta::days(days),
+                    [
+                        time("02:21"),
+                        time("03:54"),
+                        time("13:19"),
+                        time("17:51"),
+                        time("22:44"),
+                        time("00:12"),
+                    ],
+                    None,
+                )
+            })
+            .collect();
+
+        ParsedPrayerTimesResponse {
+            location: ParsedLocation {
+                state: String::new(),
+                city: "Oslo".to_string(),
+                country: "Norway".to_string(),
+                timezone: Oslo,
+                country_code: "NO".to_string(),
+                qibla_direction: 138.4,
+                latitude: 59.9139,
+                longitude: 10.7522,
+            },
+            items,
+            period: PrayerTimesPeriod::Weekly,
+            method_name: "Muslim World League".to_string(),
+            asr_school: AsrSchool::Shafii,
+            high_latitude_rule: None,
+            source: DataSource::Live,
+        }
+    }
+
+    fn settings() -> ScheduleSettings {
+        let mut iqamah = Tuning::default();
+        iqamah.set_offset(Prayer::Dhuhr, 15);
+        iqamah.set_offset(Prayer::Isha, 10);
+
+        ScheduleSettings {
+            pre_alert: Some(10),
+            iqamah,
+            end_of_window: true,
+        }
+    }
+
+    #[test]
+    fn events_come_in_order() {
+        let response = oslo();
+        let now = Oslo.with_ymd_and_hms(2026, 6, 20, 13, 0, 0).unwrap();
+
+        let events = upcoming_events(&response, &settings(), &now).unwrap();
+
+        assert!(events.windows(2).all(|pair| pair[0].at <= pair[1].at));
+        assert!(events.iter().all(|event| event.at > now));
+
+        let dhuhr: Vec<EventKind> = events
+            .iter()
+            .filter(|event| event.prayer == Prayer::Dhuhr && event.day == now.date_naive())
+            .map(|event| event.kind)
+            .collect();
+        assert_eq!(
+            dhuhr,
+            [
+                EventKind::PreAlert,
+                EventKind::AtTime,
+                EventKind::Iqamah,
+                EventKind::EndOfWindow,
+            ]
+        );
+    }
+
+    #[test]
+    fn isha_after_midnight_belongs_to_the_day_before() {
+        let response = oslo();
+        let now = Oslo.with_ymd_and_hms(2026, 6, 20, 23, 0, 0).unwrap();
+
+        let events = upcoming_events(&response, &settings(), &now).unwrap();
+        let isha = events
+            .iter()
+            .find(|event| event.prayer == Prayer::Isha && event.kind == EventKind::AtTime)
+            .unwrap();
+
+        assert_eq!(isha.day, now.date_naive());
+        assert_eq!(
+            isha.at,
+            Oslo.with_ymd_and_hms(2026, 6, 21, 0, 12, 0).unwrap()
+        );
+    }
+
+    #[test]
+    fn yesterdays_isha_is_still_scheduled_after_midnight() {
+        let response = oslo();
+        let now = Oslo.with_ymd_and_hms(2026, 6, 21, 0, 5, 0).unwrap();
+        let settings = ScheduleSettings {
+            end_of_window: false,
+            ..settings()
+        };
+
+        let events = upcoming_events(&response, &settings, &now).unwrap();
+
+        assert_eq!(events[0].prayer, Prayer::Isha);
+        assert_eq!(events[0].kind, EventKind::AtTime);
+        assert_eq!(events[0].day, NaiveDate::from_ymd_opt(2026, 6, 20).unwrap());
+        assert_eq!(events[1].kind, EventKind::Iqamah);
+    }
+
+    #[test]
+    fn days_not_covered_are_an_error() {
+        let response = oslo();
+        let now = Oslo.with_ymd_and_hms(2026, 7, 1, 12, 0, 0).unwrap();
+
+        assert!(matches!(
+            upcoming_events(&response, &settings(), &now),
+            Err(AdhanError::TodayNotCovered)
+        ));
+    }
+}
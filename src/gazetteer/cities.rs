@@ -0,0 +1,146 @@
+use std::sync::OnceLock;
+
+use chrono_tz::Tz;
+
+use crate::{
+    api::{ParsedLocation, parse_timezone},
+    calc::{Coordinates, great_circle_distance, qibla_direction},
+};
+
+/// Tab separated rows of name, alternate names separated by commas, country
+/// code, country, latitude, longitude, elevation and IANA time zone
+const CITIES: &str = include_str!("../../data/cities.tsv");
+
+/// A city of the bundled list
+#[derive(Debug, Clone, PartialEq)]
+pub struct City {
+    pub name: &'static str,
+    /// Other spellings and names in other languages, Arabic among them
+    pub alternate_names: Vec<&'static str>,
+    /// ISO 3166-1 alpha-2 code
+    pub country_code: &'static str,
+    pub country: &'static str,
+    pub latitude: f64,
+    pub longitude: f64,
+    /// Elevation above sea level in meters
+    pub elevation: f64,
+    pub timezone: Tz,
+}
+
+impl City {
+    /// Every city of the bundled list
+    pub fn all() -> &'static [City] {
+        static ALL: OnceLock<Vec<City>> = OnceLock::new();
+
+        ALL.get_or_init(|| {
+            CITIES
+                .lines()
+                .filter(|line| !line.is_empty() && !line.starts_with('#'))
+                .map(|line| {
+                    City::parse(line)
+                        .unwrap_or_else(|| panic!("malformed row of data/cities.tsv: {line}"))
+                })
+                .collect()
+        })
+    }
+
+    /// City of the list nearest to the given point among those matching
+    /// `filter`
+    pub fn nearest(
+        latitude: f64,
+        longitude: f64,
+        filter: impl Fn(&City) -> bool,
+    ) -> Option<&'static City> {
+        let distance = |city: &City| {
+            great_circle_distance((latitude, longitude), (city.latitude, city.longitude))
+        };
+
+        City::all()
+            .iter()
+            .filter(|city| filter(city))
+            .min_by(|a, b| distance(a).total_cmp(&distance(b)))
+    }
+
+    fn parse(line: &'static str) -> Option<City> {
+        let mut fields = line.split('\t');
+        let mut next = || fields.next().map(str::trim);
+
+        Some(City {
+            name: next()?,
+            alternate_names: next()?
+                .split(',')
+                .map(str::trim)
+                .filter(|name| !name.is_empty())
+                .collect(),
+            country_code: next()?,
+            country: next()?,
+            latitude: next()?.parse().ok()?,
+            longitude: next()?.parse().ok()?,
+            elevation: next()?.parse().ok()?,
+            timezone: parse_timezone(next()?).ok()?,
+        })
+    }
+
+    /// Every name the city is known by, its main name first
+    pub fn names(&self) -> impl Iterator<Item = &'static str> + '_ {
+        std::iter::once(self.name).chain(self.alternate_names.iter().copied())
+    }
+
+    /// `Algiers, Algeria`
+    pub fn display_name(&self) -> String {
+        format!("{}, {}", self.name, self.country)
+    }
+
+    pub fn coordinates(&self) -> Coordinates {
+        Coordinates {
+            latitude: self.latitude,
+            longitude: self.longitude,
+            elevation: self.elevation,
+        }
+    }
+
+    pub fn location(&self) -> ParsedLocation {
+        ParsedLocation {
+            state: String::new(),
+            city: self.name.to_string(),
+            country: self.country.to_string(),
+            timezone: self.timezone,
+            country_code: self.country_code.to_string(),
+            qibla_direction: qibla_direction(self.latitude, self.longitude),
+            latitude: self.latitude,
+            longitude: self.longitude,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parses_every_row() {
+        let rows = CITIES
+            .lines()
+            .filter(|line| !line.is_empty() && !line.starts_with('#'))
+            .count();
+        assert_eq!(City::all().len(), rows);
+
+        let algiers = City::all()
+            .iter()
+            .find(|city| city.name == "Algiers")
+            .unwrap();
+        assert_eq!(algiers.country_code, "DZ");
+        assert_eq!(algiers.timezone, Tz::Africa__Algiers);
+        assert!(algiers.alternate_names.contains(&"الجزائر"));
+    }
+
+    #[test]
+    fn finds_the_nearest_city() {
+        let oslo = City::nearest(59.9, 10.7, |_| true).unwrap();
+        assert_eq!(oslo.name, "Oslo");
+
+        let tromso = City::nearest(59.9, 10.7, |city| city.name != "Oslo").unwrap();
+        assert_ne!(tromso.name, "Oslo");
+        assert!(City::nearest(59.9, 10.7, |_| false).is_none());
+    }
+}
@@ -0,0 +1,5 @@
+mod cities;
+mod search;
+
+pub use cities::*;
+pub use search::*;
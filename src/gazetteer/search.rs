@@ -0,0 +1,232 @@
+use crate::gazetteer::City;
+
+/// Lowest score of a city listed as a candidate
+const CANDIDATE_SCORE: f64 = 0.6;
+
+/// Lowest score of a city taken without asking
+const CONFIDENT_SCORE: f64 = 0.85;
+
+/// Score by which the best city must lead the next one to be taken without
+/// asking
+const CONFIDENT_LEAD: f64 = 0.1;
+
+/// A city found for a query
+#[derive(Debug, Clone)]
+pub struct Match {
+    pub city: &'static City,
+    /// How close the query is to the nearest name of the city, 1 when they
+    /// are the same
+    pub score: f64,
+}
+
+/// What a query designates in the bundled list
+#[derive(Debug, Clone)]
+pub enum Resolution {
+    Found(&'static City),
+    /// Several cities fit the query about as well, best first
+    Ambiguous(Vec<Match>),
+    NotFound,
+}
+
+/// Lowercases `text` and drops what varies between spellings of a name:
+/// accents, spaces, punctuation, Arabic diacritics and hamza forms
+pub fn normalize(text: &str) -> String {
+    text.chars()
+        .flat_map(char::to_lowercase)
+        .filter_map(|c| match c {
+            'à' | 'á' | 'â' | 'ã' | 'ä' | 'å' => Some('a'),
+            'ç' => Some('c'),
+            'è' | 'é' | 'ê' | 'ë' => Some('e'),
+            'ì' | 'í' | 'î' | 'ï' | 'ı' => Some('i'),
+            'ñ' => Some('n'),
+            'ò' | 'ó' | 'ô' | 'õ' | 'ö' | 'ø' => Some('o'),
+            'ù' | 'ú' | 'û' | 'ü' => Some('u'),
+            'ş' => Some('s'),
+            'ğ' => Some('g'),
+            'أ' | 'إ' | 'آ' | 'ٱ' => Some('ا'),
+            'ة' => Some('ه'),
+            'ى' | 'ئ' => Some('ي'),
+            'ؤ' => Some('و'),
+            // Harakat and tatweel
+            '\u{064B}'..='\u{065F}' | 'ـ' => None,
+            c if c.is_alphanumeric() => Some(c),
+            _ => None,
+        })
+        .collect()
+}
+
+fn levenshtein(a: &[char], b: &[char]) -> usize {
+    let mut previous: Vec<usize> = (0..=b.len()).collect();
+
+    for (i, a_char) in a.iter().enumerate() {
+        let mut current = vec![i + 1; b.len() + 1];
+        for (j, b_char) in b.iter().enumerate() {
+            let substitution = previous[j] + usize::from(a_char != b_char);
+            current[j + 1] = substitution.min(previous[j + 1] + 1).min(current[j] + 1);
+        }
+        previous = current;
+    }
+
+    previous[b.len()]
+}
+
+/// Closeness of two normalized names between 0 and 1, a name starting with
+/// the query scoring high so that it can be typed partly
+fn similarity(query: &str, name: &str) -> f64 {
+    if query == name {
+        return 1.0;
+    }
+
+    let query: Vec<char> = query.chars().collect();
+    let name: Vec<char> = name.chars().collect();
+    let longest = query.len().max(name.len());
+    if longest == 0 {
+        return 0.0;
+    }
+
+    let mut score = 1.0 - levenshtein(&query, &name) as f64 / longest as f64;
+    if query.len() >= 3 && name.starts_with(&query) {
+        score = score.max(0.8 + 0.15 * query.len() as f64 / name.len() as f64);
+    }
+
+    score
+}
+
+/// Splits `Tripoli, Lebanon` into the city and the country it is narrowed to
+fn split_country(query: &str) -> (&str, Option<String>) {
+    match query.rsplit_once(',') {
+        Some((city, country)) if !normalize(country).is_empty() => (city, Some(normalize(country))),
+        _ => (query, None),
+    }
+}
+
+/// Cities whose names are close to `query`, best first. A country name or
+/// code after a comma, as in `London, UK`, keeps the cities of that country
+pub fn search(query: &str, limit: usize) -> Vec<Match> {
+    let (city, country) = split_country(query);
+    let city = normalize(city);
+    if city.is_empty() {
+        return Vec::new();
+    }
+
+    let mut matches: Vec<Match> = City::all()
+        .iter()
+        .filter(|candidate| match &country {
+            Some(country) => {
+                normalize(candidate.country) == *country
+                    || normalize(candidate.country_code) == *country
+                    || (country == "uk" && candidate.country_code == "GB")
+            }
+            None => true,
+        })
+        .map(|candidate| Match {
+            city: candidate,
+            score: candidate
+                .names()
+                .map(|name| similarity(&city, &normalize(name)))
+                .fold(0.0, f64::max),
+        })
+        .filter(|candidate| candidate.score >= CANDIDATE_SCORE)
+        .collect();
+
+    // Stable, so that cities tied on score keep the order of the list
+    matches.sort_by(|a, b| b.score.total_cmp(&a.score));
+    matches.truncate(limit);
+    matches
+}
+
+/// The city `query` designates, when one stands out from the others
+pub fn resolve(query: &str) -> Resolution {
+    let matches = search(query, 10);
+
+    let exact: Vec<Match> = matches
+        .iter()
+        .filter(|candidate| candidate.score >= 1.0)
+        .cloned()
+        .collect();
+    match exact.len() {
+        0 => {}
+        1 => return Resolution::Found(exact[0].city),
+        _ => return Resolution::Ambiguous(exact),
+    }
+
+    let Some(best) = matches.first() else {
+        return Resolution::NotFound;
+    };
+    if best.score < CONFIDENT_SCORE {
+        return Resolution::NotFound;
+    }
+
+    let close: Vec<Match> = matches
+        .iter()
+        .take_while(|candidate| candidate.score > best.score - CONFIDENT_LEAD)
+        .cloned()
+        .collect();
+    if close.len() == 1 {
+        Resolution::Found(best.city)
+    } else {
+        Resolution::Ambiguous(close)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn found(query: &str) -> &'static City {
+        match resolve(query) {
+            Resolution::Found(city) => city,
+            other => panic!("{query} resolved to {other:?}"),
+        }
+    }
+
+    #[test]
+    fn normalizes_spellings() {
+        assert_eq!(normalize("São Paulo"), "saopaulo");
+        assert_eq!(normalize("Tromsø"), "tromso");
+        assert_eq!(normalize("Al-Jaza'ir"), "aljazair");
+        assert_eq!(normalize("الجَزَائِر"), "الجزاير");
+        assert_eq!(normalize("إسطنبول"), "اسطنبول");
+    }
+
+    #[test]
+    fn scores_close_names() {
+        assert_eq!(similarity("algiers", "algiers"), 1.0);
+        assert!(similarity("algier", "algiers") >= CONFIDENT_SCORE);
+        assert!(similarity("alg", "algiers") >= 0.8);
+        assert!(similarity("oslo", "algiers") < CANDIDATE_SCORE);
+        assert_eq!(similarity("", ""), 1.0);
+        assert_eq!(similarity("a", ""), 0.0);
+    }
+
+    #[test]
+    fn resolves_misspelled_and_arabic_names() {
+        let algiers = found("algier");
+        assert_eq!((algiers.name, algiers.country_code), ("Algiers", "DZ"));
+        assert_eq!(found("الجزائر").name, "Algiers");
+        assert_eq!(found("  ALGIERS ").name, "Algiers");
+    }
+
+    #[test]
+    fn narrows_names_to_a_country() {
+        let tripoli = found("Tripoli, Lebanon");
+        assert_eq!((tripoli.name, tripoli.country_code), ("Tripoli", "LB"));
+        assert_eq!(found("tripoli, ly").country_code, "LY");
+        assert_eq!(found("London, UK").country_code, "GB");
+    }
+
+    #[test]
+    fn lists_cities_sharing_a_name() {
+        let Resolution::Ambiguous(candidates) = resolve("Tripoli") else {
+            panic!("Tripoli is not ambiguous");
+        };
+        let countries: Vec<&str> = candidates
+            .iter()
+            .map(|candidate| candidate.city.country_code)
+            .collect();
+        assert_eq!(countries, ["LY", "LB"]);
+
+        assert!(matches!(resolve("Qwxzv"), Resolution::NotFound));
+        assert!(matches!(resolve(","), Resolution::NotFound));
+    }
+}
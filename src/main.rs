@@ -1,13 +1,21 @@
 mod api;
+#[cfg(feature = "audio")]
+mod audio;
+mod calc;
 mod cli;
+mod daemon;
+mod gazetteer;
+
+use std::process::ExitCode;
 
 #[tokio::main]
-async fn main() -> Result<(), api::AdhanError> {
+async fn main() -> ExitCode {
     let result: Result<(), api::AdhanError> = cli::init().await;
 
     if let Err(e) = result {
-        println!("{}", e);
+        eprintln!("{}", e);
+        return ExitCode::FAILURE;
     }
 
-    Ok(())
+    ExitCode::SUCCESS
 }